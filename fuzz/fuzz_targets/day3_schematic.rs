@@ -0,0 +1,8 @@
+#![no_main]
+
+use advent_of_code::y2023::day3;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day3::generator(input);
+});
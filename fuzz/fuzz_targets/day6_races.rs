@@ -0,0 +1,9 @@
+#![no_main]
+
+use advent_of_code::y2023::day6;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day6::generator_part1(input);
+    let _ = day6::generator_part2(input);
+});
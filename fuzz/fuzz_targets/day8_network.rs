@@ -0,0 +1,8 @@
+#![no_main]
+
+use advent_of_code::y2023::day8;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day8::generator(input);
+});
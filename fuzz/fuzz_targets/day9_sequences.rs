@@ -0,0 +1,12 @@
+#![no_main]
+
+use advent_of_code::y2023::day9;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // The generator only parses, so solve as well to fit the sequences
+    if let Ok(sequences) = day9::generator(input) {
+        let _ = day9::part1(&sequences);
+        let _ = day9::part2(&sequences);
+    }
+});
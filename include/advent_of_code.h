@@ -0,0 +1,70 @@
+/* The solvers' C interface, generated by cbindgen from src/ffi.rs. Don't edit it by hand. */
+
+#ifndef ADVENT_OF_CODE_H
+#define ADVENT_OF_CODE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * One part of one day's puzzle, like 2023/5/2.
+ */
+typedef struct AocPuzzle {
+  uint16_t year;
+  uint8_t day;
+  uint8_t part;
+} AocPuzzle;
+
+/**
+ * The answer to a puzzle, or the reason there isn't one.
+ */
+typedef struct AocAnswer {
+  /**
+   * Whether `text` is the answer rather than an error message.
+   */
+  bool ok;
+  /**
+   * A NUL-terminated string owned by the library. Release it with `aoc_answer_free`.
+   */
+  char *text;
+} AocAnswer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * How many puzzles have a solver.
+ */
+uintptr_t aoc_puzzle_count(void);
+
+/**
+ * The puzzle at `index`, ordered by year, day and part. Every field is 0 past the last one.
+ */
+struct AocPuzzle aoc_puzzle(uintptr_t index);
+
+/**
+ * Solves a puzzle for the `len` bytes of UTF-8 at `input`.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes, or may be null when `len` is 0.
+ */
+struct AocAnswer aoc_solve(struct AocPuzzle puzzle, const uint8_t *input, uintptr_t len);
+
+/**
+ * Releases the text of an answer from `aoc_solve`. Each answer must be freed once.
+ *
+ * # Safety
+ *
+ * `answer` must come from `aoc_solve` and not have been freed already.
+ */
+void aoc_answer_free(struct AocAnswer answer);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ADVENT_OF_CODE_H */
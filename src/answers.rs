@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use crate::{Error, Result};
+
+/// Where the confirmed answers are kept, relative to the crate root.
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+/// Confirmed answers, keyed by year, day and part.
+///
+/// They are stored as TOML with one table per day:
+///
+/// ```toml
+/// [2023.1]
+/// part1 = "54667"
+/// part2 = "54203"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u16, u8, u8), String>);
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        fs::read_to_string(path)
+            .map_err(|e| Error::io(path, e))?
+            .parse()
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, answer: impl Into<String>) {
+        self.0.insert((year, day, part), answer.into());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((u16, u8, u8), &str)> {
+        self.0.iter().map(|(&key, answer)| (key, answer.as_str()))
+    }
+}
+
+fn key<T: FromStr>(key: &str, what: &str) -> Result<T> {
+    key.parse()
+        .map_err(|_| Error::Invalid(format!("{:?} is not a valid {}", key, what)))
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let table = s
+            .parse::<toml::Table>()
+            .map_err(|e| Error::Invalid(format!("malformed answers: {}", e.message())))?;
+
+        let mut answers = Answers::default();
+
+        for (year, days) in &table {
+            let days = days
+                .as_table()
+                .ok_or_else(|| Error::Invalid(format!("year {} must be a table", year)))?;
+
+            for (day, parts) in days {
+                let parts = parts.as_table().ok_or_else(|| {
+                    Error::Invalid(format!("day {} of {} must be a table", day, year))
+                })?;
+
+                for (part, answer) in parts {
+                    let number = part.strip_prefix("part").unwrap_or(part);
+
+                    // Large answers may be written as strings, small ones as plain integers
+                    let answer = match answer {
+                        toml::Value::String(answer) => answer.clone(),
+                        toml::Value::Integer(answer) => answer.to_string(),
+                        _ => {
+                            return Err(Error::Invalid(format!(
+                                "the answer to {}/{}/{} must be a string or an integer",
+                                year, day, number
+                            )))
+                        }
+                    };
+
+                    answers.insert(
+                        key(year, "year")?,
+                        key(day, "day")?,
+                        key(number, "part")?,
+                        answer,
+                    );
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+#[test]
+fn test_parse() {
+    let answers = "[2023.1]\npart1 = \"54667\"\npart2 = 54203\n\n[2023.9]\npart1 = \"-12\""
+        .parse::<Answers>()
+        .unwrap();
+
+    assert_eq!(answers.get(2023, 1, 1), Some("54667"));
+    assert_eq!(answers.get(2023, 1, 2), Some("54203"));
+    assert_eq!(answers.get(2023, 9, 1), Some("-12"));
+    assert_eq!(answers.get(2023, 9, 2), None);
+
+    assert_eq!(
+        "[2023.x]\npart1 = 1".parse::<Answers>(),
+        Err(Error::Invalid("\"x\" is not a valid day".to_string()))
+    );
+
+    assert_eq!(
+        "[2023.1]\npart1 = [1]".parse::<Answers>(),
+        Err(Error::Invalid(
+            "the answer to 2023/1/1 must be a string or an integer".to_string()
+        ))
+    );
+}
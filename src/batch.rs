@@ -0,0 +1,314 @@
+//! Runs the solvers on several people's inputs at once. Each person has a
+//! directory laid out like `input`, so `inputs/alice/2023/day5.txt` is Alice's
+//! input for day 5.
+
+use std::{
+    any::Any,
+    fs,
+    io::{self, Write},
+    panic,
+    path::Path,
+};
+
+use serde_json::json;
+
+use crate::{
+    cli::{Report, Timings},
+    registry::Solution,
+    Error, Result,
+};
+
+/// Where `batch` looks for inputs unless told otherwise.
+pub const INPUTS_PATH: &str = "inputs";
+
+/// Every selected solver's outcome on every person's input.
+pub struct Batch {
+    pub users: Vec<String>,
+    pub solutions: Vec<&'static Solution>,
+    /// One row per solution and one column per user. `None` where the user has no input for that day.
+    pub reports: Vec<Vec<Option<Report>>>,
+}
+
+impl Batch {
+    fn cells(&self) -> impl Iterator<Item = (&str, &Report)> {
+        self.reports.iter().flat_map(|row| {
+            row.iter()
+                .zip(&self.users)
+                .filter_map(|(report, user)| Some((user.as_str(), report.as_ref()?)))
+        })
+    }
+
+    /// Whether every input that was there got an answer.
+    pub fn passed(&self) -> bool {
+        self.cells().all(|(_, report)| report.answer.is_ok())
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        self.cells()
+            .map(|(user, report)| {
+                let mut value = report.to_json();
+                value["user"] = json!(user);
+                value
+            })
+            .collect()
+    }
+
+    /// Writes a table of answers and timings, then what went wrong with each input that failed.
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut rows = vec![std::iter::once("puzzle".to_string())
+            .chain(self.users.iter().cloned())
+            .collect::<Vec<_>>()];
+
+        for (solution, row) in self.solutions.iter().zip(&self.reports) {
+            let cells = row.iter().map(|report| match report {
+                None => "-".to_string(),
+                Some(report) => match &report.answer {
+                    Ok(answer) => format!(
+                        "{} (parse {:?}, solve {:?})",
+                        answer,
+                        report.parse_mean(),
+                        report.mean()
+                    ),
+                    Err(_) => report.status().to_string(),
+                },
+            });
+
+            rows.push(std::iter::once(solution.to_string()).chain(cells).collect());
+        }
+
+        let widths = (0..=self.users.len())
+            .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        for row in &rows {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("   ");
+
+            writeln!(out, "{}", line.trim_end())?;
+        }
+
+        let failures = self
+            .cells()
+            .filter_map(|(user, report)| Some((user, report, report.answer.as_ref().err()?)))
+            .collect::<Vec<_>>();
+
+        if !failures.is_empty() {
+            writeln!(out)?;
+        }
+
+        for (user, report, error) in &failures {
+            writeln!(out, "{} {}: {}", user, report.solution, error)?;
+        }
+
+        let count = |status| self.cells().filter(|(_, r)| r.status() == status).count();
+
+        writeln!(
+            out,
+            "\n{} answered, {} failed, {} panicked, across {} inputs",
+            self.cells().count() - failures.len(),
+            count("error"),
+            count("panicked"),
+            self.cells().count()
+        )
+    }
+}
+
+/// The subdirectories of `dir`, one for each person, sorted by name.
+fn users(dir: &Path) -> Result<Vec<String>> {
+    let mut users = Vec::new();
+
+    for entry in fs::read_dir(dir).map_err(|e| Error::io(dir, e))? {
+        let entry = entry.map_err(|e| Error::io(dir, e))?;
+
+        if entry.path().is_dir() {
+            users.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+
+    if users.is_empty() {
+        return Err(Error::Invalid(format!(
+            "{} has no directories of inputs",
+            dir.display()
+        )));
+    }
+
+    users.sort();
+    Ok(users)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "no message".to_string(),
+        },
+    }
+}
+
+fn panicked(payload: Box<dyn Any + Send>) -> Error {
+    Error::Panicked(panic_message(&*payload))
+}
+
+/// A report for an input the solver never got to answer.
+fn failed(solution: &'static Solution, error: Error) -> Report {
+    Report {
+        solution,
+        answer: Err(error),
+        expected: None,
+        parse_times: Vec::new(),
+        times: Vec::new(),
+        allocations: None,
+    }
+}
+
+/// Parses the input and runs a solver once, turning a panic into an error so
+/// the other inputs still run.
+fn run(solution: &'static Solution, input: &str) -> Report {
+    let mut timings = Timings::default();
+    let answer = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        solution.run(input, &mut timings)
+    }));
+
+    Report {
+        solution,
+        answer: answer.unwrap_or_else(|payload| Err(panicked(payload))),
+        expected: None,
+        parse_times: timings.parse.into_iter().collect(),
+        times: timings.solve.into_iter().collect(),
+        allocations: timings.allocations,
+    }
+}
+
+/// Runs every solution on each person's input in `dir` that it has one for.
+pub fn solve(dir: &Path, solutions: Vec<&'static Solution>) -> Result<Batch> {
+    let users = users(dir)?;
+    let mut reports = Vec::with_capacity(solutions.len());
+
+    for &solution in &solutions {
+        let mut row = Vec::with_capacity(users.len());
+
+        for user in &users {
+            let path = solution.input_path_in(&dir.join(user));
+
+            // An input that can't be read fails on its own, like one that can't be parsed
+            row.push(match path.exists() {
+                true => Some(match fs::read_to_string(&path) {
+                    Ok(input) => run(solution, &input),
+                    Err(e) => failed(solution, Error::io(&path, e)),
+                }),
+                false => None,
+            });
+        }
+
+        reports.push(row);
+    }
+
+    Ok(Batch {
+        users,
+        solutions,
+        reports,
+    })
+}
+
+#[test]
+fn test_batch() {
+    static PANICS: Solution = Solution {
+        year: 2023,
+        day: 6,
+        part: 3,
+        runner: |_, _| panic!("out of fuel"),
+    };
+
+    let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+
+    for (user, input) in [
+        ("alice", "Time: 7 15\nDistance: 9 40\n"),
+        ("bob", "Time: 7\n"),
+    ] {
+        fs::create_dir_all(dir.join(user).join("2023")).unwrap();
+        fs::write(dir.join(user).join("2023/day6.txt"), input).unwrap();
+    }
+
+    fs::create_dir_all(dir.join("carol")).unwrap();
+    fs::create_dir_all(dir.join("dave/2023")).unwrap();
+    fs::write(dir.join("dave/2023/day6.txt"), b"Time: \xff\n").unwrap();
+    fs::write(dir.join("notes.txt"), "not a person").unwrap();
+
+    let solutions = vec![
+        crate::registry::find(2023, 6, 1).unwrap(),
+        crate::registry::find(2023, 6, 2).unwrap(),
+        &PANICS,
+    ];
+    let batch = solve(&dir, solutions).unwrap();
+
+    assert_eq!(batch.users, ["alice", "bob", "carol", "dave"]);
+    assert!(!batch.passed());
+
+    let statuses = batch
+        .reports
+        .iter()
+        .map(|row| row.iter().map(|r| r.as_ref().map(Report::status)).collect())
+        .collect::<Vec<Vec<_>>>();
+
+    assert_eq!(
+        statuses,
+        [
+            [Some("unchecked"), Some("error"), None, Some("error")],
+            [Some("unchecked"), Some("error"), None, Some("error")],
+            [Some("panicked"), Some("panicked"), None, Some("error")],
+        ]
+    );
+    assert_eq!(
+        batch.reports[2][0].as_ref().unwrap().answer,
+        Err(Error::Panicked("out of fuel".to_string()))
+    );
+
+    let mut out = Vec::new();
+    batch.write(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].starts_with("puzzle     alice"));
+    assert!(lines[1].starts_with("2023/6/1   32 ("));
+    assert!(lines[3].starts_with("2023/6/3   panicked"));
+    assert!(lines[3].ends_with("panicked   -       error"));
+    let unreadable = |part| {
+        format!(
+            "dave 2023/6/{}: {}: stream did not contain valid UTF-8",
+            part,
+            dir.join("dave/2023/day6.txt").display()
+        )
+    };
+    assert_eq!(
+        lines[5..],
+        [
+            "bob 2023/6/1: line 1, column 1: expected two lines",
+            &unreadable(1),
+            "bob 2023/6/2: line 1, column 1: expected two lines",
+            &unreadable(2),
+            "alice 2023/6/3: the solver panicked: out of fuel",
+            "bob 2023/6/3: the solver panicked: out of fuel",
+            &unreadable(3),
+            "",
+            "2 answered, 5 failed, 2 panicked, across 9 inputs",
+        ]
+    );
+
+    assert_eq!(batch.to_json()[0]["user"], "alice");
+    assert_eq!(batch.to_json()[6]["status"], "panicked");
+
+    assert_eq!(
+        solve(&dir.join("carol"), Vec::new()).err(),
+        Some(Error::Invalid(format!(
+            "{} has no directories of inputs",
+            dir.join("carol").display()
+        )))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
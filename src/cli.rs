@@ -0,0 +1,1030 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+
+use crate::{
+    answers::{Answers, ANSWERS_PATH},
+    batch, fetch, gen,
+    memory::{self, AllocStats},
+    registry::{self, Solution, Step, Steps, SOLUTIONS},
+    scaffold, submit, Error, Result,
+};
+
+pub const USAGE: &str = "\
+usage: advent-of-code <command> [puzzles] [options]
+
+commands:
+    run      solve the selected days and print the answers (the default)
+    bench    time the selected days over several runs
+    check    compare the selected days with the confirmed answers
+    list     show which days have a solver and a confirmed answer
+    fetch    download the inputs for the selected days that aren't saved yet, using
+             the session cookie in AOC_SESSION
+    submit   send an answer for one puzzle, like `submit 2023/3/2`, using the
+             solver's answer unless --answer is given
+    new-day  create the module, input and example files for a day, like `new-day 2023/10`
+    gen      print a random input for a day, like `gen 2023/8 --seed 7 ghosts=3`, with
+             `<name>=<value>` settings, or list every generator and its settings
+    batch    solve the selected days for everyone with inputs in `inputs/<name>/`,
+             laid out like `input`, and show the answers side by side
+
+puzzles:
+    `<year>/<days>/<part>`, where each piece but the days can be left out, like
+    `2023/5/2`, `2023/3..7` or `2023`. Days are a comma separated list of days and
+    inclusive ranges, like `3..7` or `1,4..=6`. Without a year, the days are
+    from the latest year. Every puzzle is selected when this is left out.
+
+    Built with `--features alloc-stats`, `run` and `bench` also report how much
+    each part allocates.
+
+options:
+    -p, --part <1|2>       only this part
+    -i, --input <path>     read the input from this file, or stdin for `-`
+        --format <format>  `text` (the default) or `json`
+        --answers <path>   the confirmed answers, `answers.toml` by default
+        --iterations <n>   how many times `bench` runs each part, 10 by default
+        --answer <answer>  the answer `submit` sends
+        --seed <n>         the seed `gen` starts from, 0 by default
+        --inputs <path>    the directory `batch` reads, `inputs` by default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The selected year. `None` means every year, or the latest one when days are selected.
+    pub year: Option<u16>,
+    /// The selected days. Empty means every day.
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+    /// Overrides the usual `input/<year>/day<N>.txt`.
+    pub input: Option<Input>,
+    pub format: Format,
+    pub answers: Option<PathBuf>,
+    pub iterations: usize,
+    pub answer: Option<String>,
+    /// Overrides the usual `inputs` for `batch`.
+    pub inputs: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            year: None,
+            days: Vec::new(),
+            part: None,
+            input: None,
+            format: Format::Text,
+            answers: None,
+            iterations: 10,
+            answer: None,
+            inputs: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(Options),
+    Bench(Options),
+    Check(Options),
+    List(Options),
+    Fetch(Options),
+    Submit(Options),
+    Batch(Options),
+    NewDay(u16, u8),
+    Gen(GenOptions),
+    Help,
+}
+
+/// What `gen` creates. Without a day, it lists the generators instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenOptions {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub seed: u64,
+    pub settings: Vec<(String, u64)>,
+}
+
+/// Parses a day selection like `3`, `3..7`, `3..=7` or `1,4..6`. Ranges are inclusive.
+pub fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let day = |s: &str| match s.trim().parse::<u8>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("{:?} is not a day between 1 and 25", s)),
+    };
+
+    let mut days = Vec::new();
+
+    for item in s.split(',') {
+        match item.split_once("..") {
+            Some((start, end)) => {
+                let (start, end) = (day(start)?, day(end.strip_prefix('=').unwrap_or(end))?);
+
+                if start > end {
+                    return Err(format!("the range {:?} is empty", item));
+                }
+
+                days.extend(start..=end);
+            }
+            None => days.push(day(item)?),
+        }
+    }
+
+    days.sort();
+    days.dedup();
+
+    Ok(days)
+}
+
+/// A puzzle address like `2023/5/2`, `2023/3..7`, `2023` or `3..7`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub year: Option<u16>,
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        part => Err(format!("{:?} is not a part, expected 1 or 2", part)),
+    }
+}
+
+pub fn parse_selection(s: &str) -> Result<Selection, String> {
+    let mut pieces = s.split('/').collect::<Vec<_>>();
+
+    // Years are the only pieces with four digits
+    let year = match pieces.first() {
+        Some(first) if first.len() == 4 && first.bytes().all(|b| b.is_ascii_digit()) => Some(
+            pieces
+                .remove(0)
+                .parse()
+                .map_err(|_| format!("{:?} is not a year", s))?,
+        ),
+        _ => None,
+    };
+
+    let (days, part) = match pieces.as_slice() {
+        [] => (Vec::new(), None),
+        [days] => (parse_days(days)?, None),
+        [days, part] => (parse_days(days)?, Some(parse_part(part)?)),
+        _ => return Err(format!("{:?} is not a puzzle like 2023/5/2", s)),
+    };
+
+    Ok(Selection { year, days, part })
+}
+
+/// Parses the arguments of `gen`: at most one day, a seed and `<name>=<value>` settings.
+fn parse_gen(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut options = GenOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                options.seed = args
+                    .next()
+                    .and_then(|seed| seed.parse().ok())
+                    .ok_or_else(|| "--seed needs a number".to_string())?
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {:?}", flag)),
+            setting if setting.contains('=') => {
+                let (name, value) = setting.split_once('=').unwrap();
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("the setting {:?} needs a number", name))?;
+
+                options.settings.push((name.to_string(), value));
+            }
+            puzzle if options.day.is_none() => {
+                let selection = parse_selection(puzzle)?;
+
+                match (selection.days.as_slice(), selection.part) {
+                    (&[day], None) => (options.year, options.day) = (selection.year, Some(day)),
+                    _ => return Err("gen takes a single day".to_string()),
+                }
+            }
+            extra => return Err(format!("unexpected argument {:?}", extra)),
+        }
+    }
+
+    if options.day.is_none() && !options.settings.is_empty() {
+        return Err("gen needs a day for the settings to apply to".to_string());
+    }
+
+    Ok(Command::Gen(options))
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.peek().map(String::as_str) {
+        Some("run") | Some("bench") | Some("check") | Some("list") | Some("fetch")
+        | Some("submit") | Some("batch") => args.next(),
+        Some("help") | Some("-h") | Some("--help") => return Ok(Command::Help),
+        Some("gen") => {
+            args.next();
+            return parse_gen(args);
+        }
+        Some("new-day") => {
+            args.next();
+
+            let selection = match (args.next(), args.next()) {
+                (Some(day), None) if !day.contains(['.', ',']) => parse_selection(&day)?,
+                _ => return Err("new-day takes a single day".to_string()),
+            };
+
+            return match (selection.days.as_slice(), selection.part) {
+                (&[day], None) => Ok(Command::NewDay(
+                    selection.year.unwrap_or_else(registry::latest_year),
+                    day,
+                )),
+                _ => Err("new-day takes a single day".to_string()),
+            };
+        }
+        _ => None,
+    };
+
+    let mut options = Options::default();
+    let mut selection = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+
+        match arg.as_str() {
+            "-p" | "--part" => options.part = Some(parse_part(&value(&arg)?)?),
+            "-i" | "--input" => {
+                options.input = Some(match value(&arg)?.as_str() {
+                    "-" => Input::Stdin,
+                    path => Input::File(PathBuf::from(path)),
+                })
+            }
+            "--format" => {
+                options.format = match value(&arg)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    format => return Err(format!("unknown format {:?}", format)),
+                }
+            }
+            "--answers" => options.answers = Some(PathBuf::from(value(&arg)?)),
+            "--answer" => options.answer = Some(value(&arg)?),
+            "--inputs" => options.inputs = Some(PathBuf::from(value(&arg)?)),
+            "--iterations" => {
+                options.iterations = match value(&arg)?.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err("--iterations needs a positive number".to_string()),
+                }
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {:?}", flag)),
+            puzzles if selection.is_none() => selection = Some(parse_selection(puzzles)?),
+            extra => return Err(format!("unexpected argument {:?}", extra)),
+        }
+    }
+
+    if let Some(selection) = selection {
+        options.part = match (options.part, selection.part) {
+            (Some(a), Some(b)) if a != b => {
+                return Err(format!(
+                    "the puzzle is part {}, but --part asks for {}",
+                    b, a
+                ))
+            }
+            (a, b) => a.or(b),
+        };
+
+        options.year = selection.year;
+        options.days = selection.days;
+    }
+
+    if options.input.is_some() && options.days.len() != 1 {
+        return Err("--input needs exactly one day to be selected".to_string());
+    }
+
+    if command.as_deref() == Some("submit") && (options.days.len() != 1 || options.part.is_none()) {
+        return Err("submit needs exactly one puzzle, like 2023/5/2".to_string());
+    }
+
+    Ok(match command.as_deref() {
+        Some("submit") => Command::Submit(options),
+        Some("bench") => Command::Bench(options),
+        Some("check") => Command::Check(options),
+        Some("list") => Command::List(options),
+        Some("fetch") => Command::Fetch(options),
+        Some("batch") => Command::Batch(options),
+        _ => Command::Run(options),
+    })
+}
+
+/// The registered solutions matching the selected year, days and part.
+fn select(options: &Options) -> Result<Vec<&'static Solution>> {
+    let year = match options.year {
+        None if options.days.is_empty() => None,
+        year => Some(year.unwrap_or_else(registry::latest_year)),
+    };
+
+    let in_year = |s: &&Solution| year.is_none_or(|year| s.year == year);
+
+    if let Some(year) = year.filter(|_| !SOLUTIONS.iter().any(|s| in_year(&s))) {
+        return Err(Error::Invalid(format!("{} has no solvers", year)));
+    }
+
+    if let Some(&day) = options
+        .days
+        .iter()
+        .find(|&&day| !SOLUTIONS.iter().filter(in_year).any(|s| s.day == day))
+    {
+        return Err(Error::Invalid(format!(
+            "day {} of {} has no solver",
+            day,
+            year.unwrap_or_else(registry::latest_year)
+        )));
+    }
+
+    Ok(SOLUTIONS
+        .iter()
+        .filter(in_year)
+        .filter(|s| options.days.is_empty() || options.days.contains(&s.day))
+        .filter(|s| options.part.is_none_or(|part| s.part == part))
+        .collect())
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| Error::io(path, e))
+}
+
+fn load_answers(options: &Options) -> Result<Answers> {
+    match &options.answers {
+        Some(path) => Answers::load(path),
+        // Without confirmed answers there is nothing to compare against, which is fine
+        None if !Path::new(ANSWERS_PATH).exists() => Ok(Answers::default()),
+        None => Answers::load(ANSWERS_PATH),
+    }
+}
+
+/// The outcome of running one solver.
+pub struct Report {
+    pub solution: &'static Solution,
+    pub answer: Result<String>,
+    /// The confirmed answer, when the solver ran on the input it was confirmed for.
+    pub expected: Option<String>,
+    /// The time parsing the input took on each run.
+    pub parse_times: Vec<Duration>,
+    /// The time solving the parsed input took on each run.
+    pub times: Vec<Duration>,
+    /// What the last run's solver allocated, after parsing, when built with the
+    /// `alloc-stats` feature.
+    pub allocations: Option<AllocStats>,
+}
+
+/// How long each step of one run took, and what solving allocated.
+#[derive(Debug, Default)]
+pub struct Timings {
+    /// `None` until parsing finishes.
+    pub parse: Option<Duration>,
+    /// `None` until solving finishes.
+    pub solve: Option<Duration>,
+    /// What solving allocated, when built with the `alloc-stats` feature.
+    pub allocations: Option<AllocStats>,
+}
+
+impl Steps for Timings {
+    fn run(&mut self, step: Step, run: &mut dyn FnMut()) {
+        let start = Instant::now();
+
+        match step {
+            Step::Parse => {
+                run();
+                self.parse = Some(start.elapsed());
+            }
+            Step::Solve => {
+                let ((), allocations) = memory::measure(run);
+                self.solve = Some(start.elapsed());
+                self.allocations = allocations;
+            }
+        }
+    }
+}
+
+impl Report {
+    pub fn status(&self) -> &'static str {
+        match (&self.answer, &self.expected) {
+            (Err(Error::Panicked(_)), _) => "panicked",
+            (Err(_), _) => "error",
+            (Ok(_), None) => "unchecked",
+            (Ok(answer), Some(expected)) if answer == expected => "correct",
+            (Ok(_), Some(_)) => "wrong",
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        matches!(self.status(), "correct" | "unchecked")
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.times.iter().sum::<Duration>() / self.times.len().max(1) as u32
+    }
+
+    pub fn parse_mean(&self) -> Duration {
+        self.parse_times.iter().sum::<Duration>() / self.parse_times.len().max(1) as u32
+    }
+
+    fn min(&self) -> Duration {
+        self.times.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "year": self.solution.year,
+            "day": self.solution.day,
+            "part": self.solution.part,
+            "answer": self.answer.as_ref().ok(),
+            "error": self.answer.as_ref().err().map(Error::to_string),
+            "expected": self.expected,
+            "status": self.status(),
+            "parse_mean_ms": self.parse_mean().as_secs_f64() * 1000.0,
+            "mean_ms": self.mean().as_secs_f64() * 1000.0,
+            "min_ms": self.min().as_secs_f64() * 1000.0,
+            "runs": self.times.len(),
+            "allocations": self.allocations.map(|a| a.allocations),
+            "allocated_bytes": self.allocations.map(|a| a.bytes),
+            "peak_bytes": self.allocations.map(|a| a.peak),
+        })
+    }
+}
+
+/// Runs every selected solver `iterations` times.
+pub fn solve(options: &Options, iterations: usize) -> Result<Vec<Report>> {
+    let solutions = select(options)?;
+    let answers = load_answers(options)?;
+
+    let explicit_input = match &options.input {
+        Some(Input::File(path)) => Some(read_file(path)?),
+        Some(Input::Stdin) => {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| Error::io("<stdin>", e))?;
+
+            Some(input)
+        }
+        None => None,
+    };
+
+    let mut reports = Vec::with_capacity(solutions.len());
+
+    for solution in solutions {
+        let (input, expected) = match &explicit_input {
+            Some(input) => (input.clone(), None),
+            None => (
+                read_file(&solution.input_path())?,
+                answers
+                    .get(solution.year, solution.day, solution.part)
+                    .map(str::to_string),
+            ),
+        };
+
+        let mut parse_times = Vec::with_capacity(iterations);
+        let mut times = Vec::with_capacity(iterations);
+        let mut answer = Err(Error::NoSolution("the solver never ran".to_string()));
+        let mut allocations = None;
+
+        for _ in 0..iterations {
+            let mut timings = Timings::default();
+            answer = solution.run(&input, &mut timings);
+            parse_times.extend(timings.parse);
+            allocations = timings.allocations;
+
+            // Without a parsed input there is nothing to solve on the next run either
+            match timings.solve {
+                Some(time) => times.push(time),
+                None => break,
+            }
+        }
+
+        reports.push(Report {
+            solution,
+            answer,
+            expected,
+            parse_times,
+            times,
+            allocations,
+        });
+    }
+
+    Ok(reports)
+}
+
+fn write_json(out: &mut impl Write, value: &serde_json::Value) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)
+}
+
+fn write_reports(out: &mut impl Write, command: &Command, reports: &[Report]) -> io::Result<()> {
+    for report in reports {
+        let name = report.solution;
+        let allocations = match report.allocations {
+            Some(allocations) => format!(", {}", allocations),
+            None => String::new(),
+        };
+
+        match (command, &report.answer) {
+            (_, Err(e)) => writeln!(out, "{}: error: {}", name, e)?,
+            (Command::Bench(_), Ok(_)) => writeln!(
+                out,
+                "{}: parse {:?}, solve mean {:?}, min {:?} over {} runs{}",
+                name,
+                report.parse_mean(),
+                report.mean(),
+                report.min(),
+                report.times.len(),
+                allocations
+            )?,
+            (Command::Check(_), Ok(answer)) => match &report.expected {
+                Some(expected) if answer == expected => writeln!(out, "{}: ok", name)?,
+                Some(expected) => writeln!(out, "{}: expected {}, got {}", name, expected, answer)?,
+                None => writeln!(out, "{}: no confirmed answer, got {}", name, answer)?,
+            },
+            (_, Ok(answer)) => {
+                write!(
+                    out,
+                    "{}: {} (parse {:?}, solve {:?}{})",
+                    name,
+                    answer,
+                    report.parse_mean(),
+                    report.mean(),
+                    allocations
+                )?;
+
+                match &report.expected {
+                    Some(expected) if answer != expected => {
+                        writeln!(out, " [expected {}]", expected)?
+                    }
+                    _ => writeln!(out)?,
+                }
+            }
+        }
+    }
+
+    if let Command::Check(_) = command {
+        let count = |status| reports.iter().filter(|r| r.status() == status).count();
+
+        writeln!(
+            out,
+            "\n{} correct, {} wrong, {} failed, {} without a confirmed answer",
+            count("correct"),
+            count("wrong"),
+            count("error"),
+            count("unchecked")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Runs a command, writing its output to `out`. Returns whether every result
+/// agreed with its confirmed answer.
+pub fn execute(command: &Command, out: &mut impl Write) -> Result<bool> {
+    let write_error = |e| Error::io("<stdout>", e);
+
+    let (options, iterations) = match command {
+        Command::Help => {
+            writeln!(out, "{}", USAGE).map_err(write_error)?;
+            return Ok(true);
+        }
+        Command::Fetch(options) => {
+            let client = fetch::Client::from_env()?;
+
+            for solution in select(options)?.into_iter().filter(|s| s.part == 1) {
+                client.input(solution.year, solution.day)?;
+
+                writeln!(
+                    out,
+                    "{}",
+                    client.input_path(solution.year, solution.day).display()
+                )
+                .map_err(write_error)?;
+            }
+
+            return Ok(true);
+        }
+        Command::Submit(options) => {
+            let answer = match &options.answer {
+                Some(answer) => answer.clone(),
+                None => {
+                    solve(options, 1)?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| Error::Invalid("nothing to submit".to_string()))?
+                        .answer?
+                }
+            };
+
+            let solution = select(options)?[0];
+            let attempts = submit::Attempts::load(submit::ATTEMPTS_PATH)?;
+            let mut submitter = submit::Submitter::new(fetch::Client::from_env()?, attempts);
+            let outcome = submitter.submit(solution.year, solution.day, solution.part, &answer)?;
+
+            writeln!(out, "{}: {} is {}", solution, answer, outcome).map_err(write_error)?;
+
+            return Ok(outcome == submit::Outcome::Correct);
+        }
+        Command::Gen(options) => {
+            match options.day {
+                Some(day) => {
+                    let year = options.year.unwrap_or_else(registry::latest_year);
+                    let generator = gen::find(year, day).ok_or_else(|| {
+                        Error::Invalid(format!("day {} of {} has no generator", day, year))
+                    })?;
+
+                    let input = generator.generate(
+                        options.seed,
+                        options
+                            .settings
+                            .iter()
+                            .map(|(name, value)| (name.as_str(), *value)),
+                    )?;
+
+                    write!(out, "{}", input).map_err(write_error)?;
+                }
+                None => {
+                    for generator in gen::GENERATORS {
+                        writeln!(out, "{}", generator).map_err(write_error)?;
+
+                        for setting in generator.settings {
+                            let default = format!("{}={}", setting.name, setting.default);
+
+                            writeln!(out, "    {:<24} {}", default, setting.description)
+                                .map_err(write_error)?;
+                        }
+                    }
+                }
+            }
+
+            return Ok(true);
+        }
+        &Command::NewDay(year, day) => {
+            for path in scaffold::new_day(Path::new("."), year, day)? {
+                writeln!(out, "wrote {}", path.display()).map_err(write_error)?;
+            }
+
+            return Ok(true);
+        }
+        Command::List(options) => {
+            let answers = load_answers(options)?;
+            let solutions = select(options)?;
+
+            match options.format {
+                Format::Text => solutions
+                    .iter()
+                    .try_for_each(|s| {
+                        let answered = match answers.get(s.year, s.day, s.part) {
+                            Some(_) => "confirmed answer",
+                            None => "no confirmed answer",
+                        };
+
+                        writeln!(out, "{}: {}", s, answered)
+                    })
+                    .map_err(write_error)?,
+                Format::Json => write_json(
+                    out,
+                    &solutions
+                        .iter()
+                        .map(|s| {
+                            json!({
+                                "year": s.year,
+                                "day": s.day,
+                                "part": s.part,
+                                "answer": answers.get(s.year, s.day, s.part),
+                            })
+                        })
+                        .collect(),
+                )
+                .map_err(write_error)?,
+            }
+
+            return Ok(true);
+        }
+        Command::Batch(options) => {
+            if options.input.is_some() {
+                return Err(Error::Invalid(
+                    "batch reads every input in --inputs, so it can't take --input".to_string(),
+                ));
+            }
+
+            let dir = options
+                .inputs
+                .as_deref()
+                .unwrap_or(Path::new(batch::INPUTS_PATH));
+            let batch = batch::solve(dir, select(options)?)?;
+
+            match options.format {
+                Format::Text => batch.write(out),
+                Format::Json => write_json(out, &batch.to_json()),
+            }
+            .map_err(write_error)?;
+
+            return Ok(batch.passed());
+        }
+        Command::Check(options) if options.input.is_some() => {
+            return Err(Error::Invalid(
+                "check compares against the committed inputs, so it can't take --input".to_string(),
+            ))
+        }
+        Command::Bench(options) => (options, options.iterations),
+        Command::Run(options) | Command::Check(options) => (options, 1),
+    };
+
+    let reports = solve(options, iterations)?;
+
+    match options.format {
+        Format::Text => write_reports(out, command, &reports),
+        Format::Json => write_json(out, &reports.iter().map(Report::to_json).collect()),
+    }
+    .map_err(write_error)?;
+
+    Ok(reports.iter().all(Report::passed))
+}
+
+#[cfg(test)]
+fn args(s: &str) -> Result<Command, String> {
+    parse_args(s.split_whitespace().map(str::to_string))
+}
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(parse_days("3"), Ok(vec![3]));
+    assert_eq!(parse_days("3..7"), Ok(vec![3, 4, 5, 6, 7]));
+    assert_eq!(parse_days("6..=7,1,7"), Ok(vec![1, 6, 7]));
+    assert_eq!(
+        parse_days("7..3"),
+        Err("the range \"7..3\" is empty".to_string())
+    );
+    assert_eq!(
+        parse_days("26"),
+        Err("\"26\" is not a day between 1 and 25".to_string())
+    );
+}
+
+#[test]
+fn test_parse_selection() {
+    assert_eq!(
+        parse_selection("2023/5/2"),
+        Ok(Selection {
+            year: Some(2023),
+            days: vec![5],
+            part: Some(2),
+        })
+    );
+    assert_eq!(
+        parse_selection("2023/3..7").map(|s| (s.year, s.days.len())),
+        Ok((Some(2023), 5))
+    );
+    assert_eq!(
+        parse_selection("2023"),
+        Ok(Selection {
+            year: Some(2023),
+            days: Vec::new(),
+            part: None,
+        })
+    );
+    assert_eq!(
+        parse_selection("7/1"),
+        Ok(Selection {
+            year: None,
+            days: vec![7],
+            part: Some(1),
+        })
+    );
+    assert_eq!(
+        parse_selection("2023/5/2/1"),
+        Err("\"2023/5/2/1\" is not a puzzle like 2023/5/2".to_string())
+    );
+}
+
+#[test]
+fn test_parse_args() {
+    assert_eq!(args(""), Ok(Command::Run(Options::default())));
+
+    assert_eq!(
+        args("run 3..7 --part 2 --format json"),
+        Ok(Command::Run(Options {
+            days: vec![3, 4, 5, 6, 7],
+            part: Some(2),
+            format: Format::Json,
+            ..Options::default()
+        }))
+    );
+
+    assert_eq!(
+        args("bench 1 -i - --iterations 3"),
+        Ok(Command::Bench(Options {
+            days: vec![1],
+            input: Some(Input::Stdin),
+            iterations: 3,
+            ..Options::default()
+        }))
+    );
+
+    assert_eq!(
+        args("run 1..2 --input day1.txt"),
+        Err("--input needs exactly one day to be selected".to_string())
+    );
+    assert_eq!(
+        args("check --part 3"),
+        Err("\"3\" is not a part, expected 1 or 2".to_string())
+    );
+    assert_eq!(args("run --part"), Err("--part needs a value".to_string()));
+    assert_eq!(
+        args("list 1 2"),
+        Err("unexpected argument \"2\"".to_string())
+    );
+
+    assert_eq!(
+        args("run 2023/4..5 --format json"),
+        Ok(Command::Run(Options {
+            year: Some(2023),
+            days: vec![4, 5],
+            format: Format::Json,
+            ..Options::default()
+        }))
+    );
+    assert_eq!(
+        args("run 2023/5/2 --part 1"),
+        Err("the puzzle is part 2, but --part asks for 1".to_string())
+    );
+    assert_eq!(args("new-day 2024/1"), Ok(Command::NewDay(2024, 1)));
+    assert_eq!(
+        args("new-day 10"),
+        Ok(Command::NewDay(registry::latest_year(), 10))
+    );
+    assert_eq!(
+        args("new-day 3..4"),
+        Err("new-day takes a single day".to_string())
+    );
+
+    assert_eq!(
+        args("submit 2023/3/2 --answer 42"),
+        Ok(Command::Submit(Options {
+            year: Some(2023),
+            days: vec![3],
+            part: Some(2),
+            answer: Some("42".to_string()),
+            ..Options::default()
+        }))
+    );
+    assert_eq!(
+        args("batch 2023/6 --inputs team"),
+        Ok(Command::Batch(Options {
+            year: Some(2023),
+            days: vec![6],
+            inputs: Some(PathBuf::from("team")),
+            ..Options::default()
+        }))
+    );
+    assert_eq!(
+        args("submit 2023/3"),
+        Err("submit needs exactly one puzzle, like 2023/5/2".to_string())
+    );
+
+    assert_eq!(
+        args("gen 2023/8 --seed 7 ghosts=3 tail=2"),
+        Ok(Command::Gen(GenOptions {
+            year: Some(2023),
+            day: Some(8),
+            seed: 7,
+            settings: vec![("ghosts".to_string(), 3), ("tail".to_string(), 2)],
+        }))
+    );
+    assert_eq!(args("gen"), Ok(Command::Gen(GenOptions::default())));
+    assert_eq!(
+        args("gen 2023/8/1"),
+        Err("gen takes a single day".to_string())
+    );
+    assert_eq!(
+        args("gen 8 ghosts=many"),
+        Err("the setting \"ghosts\" needs a number".to_string())
+    );
+    assert_eq!(
+        args("gen width=3"),
+        Err("gen needs a day for the settings to apply to".to_string())
+    );
+}
+
+#[test]
+fn test_execute() {
+    let mut out = Vec::new();
+    let check = args("check 2023/6").unwrap();
+
+    assert_eq!(execute(&check, &mut out), Ok(true));
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "2023/6/1: ok\n2023/6/2: ok\n\n2 correct, 0 wrong, 0 failed, 0 without a confirmed answer\n"
+    );
+
+    // A wrong confirmed answer makes the run fail
+    let dir = std::env::temp_dir().join(format!("aoc-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("answers.toml"), "[2023.6]\npart1 = \"1\"").unwrap();
+
+    let run = Command::Run(Options {
+        year: Some(2023),
+        days: vec![6],
+        part: Some(1),
+        answers: Some(dir.join("answers.toml")),
+        ..Options::default()
+    });
+
+    let mut out = Vec::new();
+    assert_eq!(execute(&run, &mut out), Ok(false));
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .starts_with("2023/6/1: 1312850 ("));
+
+    // An explicit input is never compared against the confirmed answers
+    fs::write(dir.join("day6.txt"), "Time: 7\nDistance: 9\n").unwrap();
+
+    let run = Command::Run(Options {
+        year: Some(2023),
+        days: vec![6],
+        input: Some(Input::File(dir.join("day6.txt"))),
+        format: Format::Json,
+        ..Options::default()
+    });
+
+    let mut out = Vec::new();
+    assert_eq!(execute(&run, &mut out), Ok(true));
+
+    let reports: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(reports[1]["answer"], "4");
+    assert_eq!(reports[1]["status"], "unchecked");
+
+    // Batch mode fails when any input fails
+    fs::create_dir_all(dir.join("team/alice/2023")).unwrap();
+    fs::rename(dir.join("day6.txt"), dir.join("team/alice/2023/day6.txt")).unwrap();
+    fs::create_dir_all(dir.join("team/bob/2023")).unwrap();
+    fs::write(dir.join("team/bob/2023/day6.txt"), "Time: 7\nDistance: x\n").unwrap();
+
+    let batch = Command::Batch(Options {
+        year: Some(2023),
+        days: vec![6],
+        part: Some(1),
+        inputs: Some(dir.join("team")),
+        ..Options::default()
+    });
+
+    let mut out = Vec::new();
+    assert_eq!(execute(&batch, &mut out), Ok(false));
+    assert!(String::from_utf8(out).unwrap().contains(
+        "bob 2023/6/1: line 2, column 11: expected a number, found \"x\"\n\n1 answered, 1 failed"
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+
+    assert_eq!(
+        execute(&args("run 2023/25").unwrap(), &mut Vec::new()).err(),
+        Some(Error::Invalid("day 25 of 2023 has no solver".to_string()))
+    );
+
+    assert_eq!(
+        execute(&args("list 1999").unwrap(), &mut Vec::new()).err(),
+        Some(Error::Invalid("1999 has no solvers".to_string()))
+    );
+
+    let mut out = Vec::new();
+    assert_eq!(
+        execute(&args("gen 2023/6 --seed 3 races=2").unwrap(), &mut out),
+        Ok(true)
+    );
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        gen::find(2023, 6)
+            .unwrap()
+            .generate(3, [("races", 2)])
+            .unwrap()
+    );
+
+    assert_eq!(
+        execute(&args("gen 2023/25").unwrap(), &mut Vec::new()).err(),
+        Some(Error::Invalid(
+            "day 25 of 2023 has no generator".to_string()
+        ))
+    );
+}
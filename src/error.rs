@@ -0,0 +1,151 @@
+use std::{fmt, io, path::Path, str::FromStr};
+
+use bstr::ByteSlice;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input doesn't follow the puzzle format.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed, but breaks one of the puzzle's rules.
+    Invalid(String),
+    /// The input is valid, but there is no answer to be found.
+    NoSolution(String),
+    /// A file couldn't be read or written.
+    Io { path: String, message: String },
+    /// A request to the puzzle server failed or was refused.
+    Remote(String),
+    /// A solver panicked instead of answering.
+    Panicked(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Returns the 1-based line and column where `span` starts inside `input`.
+fn position(input: &[u8], span: &[u8]) -> (usize, usize) {
+    let offset = (span.as_ptr() as usize)
+        .saturating_sub(input.as_ptr() as usize)
+        .min(input.len());
+
+    let before = &input[..offset];
+    let line_start = before.rfind_byte(b'\n').map_or(0, |i| i + 1);
+
+    (
+        before.find_iter("\n").count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn io(path: impl AsRef<Path>, error: io::Error) -> Self {
+        Error::Io {
+            path: path.as_ref().display().to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    /// Creates a parse error pointing at `span`, which must be a slice of `input`.
+    pub fn at(input: &str, span: &str, message: impl Into<String>) -> Self {
+        Error::at_bytes(input.as_bytes(), span.as_bytes(), message)
+    }
+
+    /// Like [`Error::at`], for inputs read as bytes.
+    pub fn at_bytes(input: &[u8], span: &[u8], message: impl Into<String>) -> Self {
+        let (line, column) = position(input, span);
+
+        Error::parse(line, column, message)
+    }
+
+    /// Moves an error raised while parsing `inner` so that it points into `outer` instead.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => {
+                let (offset_line, offset_column) = position(outer.as_bytes(), inner.as_bytes());
+
+                Error::Parse {
+                    line: line + offset_line - 1,
+                    column: if line == 1 {
+                        column + offset_column - 1
+                    } else {
+                        column
+                    },
+                    message,
+                }
+            }
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Invalid(message) => write!(f, "invalid input: {}", message),
+            Error::NoSolution(message) => write!(f, "no solution: {}", message),
+            Error::Io { path, message } => write!(f, "{}: {}", path, message),
+            Error::Remote(message) => write!(f, "request failed: {}", message),
+            Error::Panicked(message) => write!(f, "the solver panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T> {
+    token.parse().map_err(|_| {
+        Error::at(
+            input,
+            token,
+            format!("expected a number, found {:?}", token),
+        )
+    })
+}
+
+/// Parses every line of `input`, pointing errors at the line they came from.
+pub fn lines<T: FromStr<Err = Error>>(input: &str) -> Result<Vec<T>> {
+    input
+        .lines()
+        .map(|line| line.parse().map_err(|e: Error| e.within(input, line)))
+        .collect()
+}
+
+#[test]
+fn test_position() {
+    let input = "Game 1: 3 blue\nGame x: 4 red";
+
+    assert_eq!(
+        number::<u64>(input, &input[20..21]),
+        Err(Error::parse(2, 6, "expected a number, found \"x\"")),
+    );
+
+    assert_eq!(
+        Error::parse(1, 3, "oops").within(input, &input[15..]),
+        Error::parse(2, 3, "oops"),
+    );
+
+    assert_eq!(
+        Error::parse(1, 3, "oops").within(input, &input[20..]),
+        Error::parse(2, 8, "oops"),
+    );
+}
@@ -0,0 +1,319 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::Agent;
+
+use crate::{Error, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// The environment variable holding the value of the `session` cookie.
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+/// The environment variable that overrides [`DEFAULT_BASE_URL`].
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+/// Names the runner and where to find its source, taken from `repository` in
+/// `Cargo.toml`, so the puzzle server knows who to contact about its requests.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    "; personal puzzle runner, fetches each input once and caches it)"
+);
+
+/// When a day's puzzle unlocks: midnight in UTC-5 on that day of December.
+pub fn release_time(year: u16, day: u8) -> SystemTime {
+    // Days since the epoch for the civil date, following Howard Hinnant's algorithm
+    let (y, m, d) = (year as i64, 12, day as i64);
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * (m - 3) + 2) / 5 + d - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    UNIX_EPOCH + Duration::from_secs((days * 86_400 + 5 * 3_600) as u64)
+}
+
+/// Downloads puzzle inputs, keeping a copy of each under `input/<year>/day<N>.txt`
+/// so that none is ever requested twice.
+pub struct Client {
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    agent: Agent,
+}
+
+impl Client {
+    pub fn new(
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        cache_dir: impl Into<PathBuf>,
+    ) -> Self {
+        let config = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .http_status_as_error(false)
+            // The site redirects to the login page when the session isn't accepted
+            .max_redirects(0)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build();
+
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            cache_dir: cache_dir.into(),
+            agent: Agent::new_with_config(config),
+        }
+    }
+
+    /// Reads the session cookie and base URL from the environment, caching under `input/`.
+    pub fn from_env() -> Result<Self> {
+        let session = env::var(SESSION_VARIABLE).map_err(|_| {
+            Error::Remote(format!(
+                "set {} to the value of your session cookie",
+                SESSION_VARIABLE
+            ))
+        })?;
+
+        let base_url = env::var(BASE_URL_VARIABLE).unwrap_or(DEFAULT_BASE_URL.to_string());
+
+        Ok(Client::new(base_url, session.trim(), "input"))
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{}.txt", day))
+    }
+
+    /// Returns the input for a day, downloading it only if there is no cached copy.
+    /// An empty file, like the one `new-day` creates, counts as missing.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.input_path(year, day);
+
+        match fs::read_to_string(&path) {
+            Ok(input) if !input.is_empty() => return Ok(input),
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(Error::io(&path, e)),
+        }
+
+        let input = self.download(year, day)?;
+
+        let dir = path.parent().unwrap_or(&self.cache_dir);
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+
+        // Write next to the final file first, so an interrupted write never looks cached
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, &input).map_err(|e| Error::io(&partial, e))?;
+        fs::rename(&partial, &path).map_err(|e| Error::io(&path, e))?;
+
+        Ok(input)
+    }
+
+    fn download(&self, year: u16, day: u8) -> Result<String> {
+        if !(1..=25).contains(&day) {
+            return Err(Error::Invalid(format!(
+                "{} is not a day between 1 and 25",
+                day
+            )));
+        }
+
+        let unreleased =
+            || Error::Remote(format!("day {} of {} hasn't been released yet", day, year));
+
+        if SystemTime::now() < release_time(year, day) {
+            return Err(unreleased());
+        }
+
+        let (status, body) = self.send(&format!("/{}/day/{}/input", year, day), None)?;
+
+        match status {
+            200 => Ok(body),
+            404 if body.contains("unlock") => Err(unreleased()),
+            300..=399 | 400 | 401 | 403 => Err(Error::Remote(
+                "the session cookie was refused, it has probably expired".to_string(),
+            )),
+            _ => Err(Error::Remote(format!(
+                "{}/{}/day/{}/input answered with status {}: {}",
+                self.base_url,
+                year,
+                day,
+                status,
+                body.trim()
+            ))),
+        }
+    }
+
+    /// Sends a GET to `path` under the base URL, or a POST when there is a form
+    /// to send, and returns the status and body of the response.
+    pub(crate) fn send(&self, path: &str, form: Option<&str>) -> Result<(u16, String)> {
+        let url = format!("{}{}", self.base_url, path);
+        let cookie = format!("session={}", self.session);
+
+        let response = match form {
+            Some(form) => self
+                .agent
+                .post(&url)
+                .header("Cookie", cookie)
+                .content_type("application/x-www-form-urlencoded")
+                .send(form),
+            None => self.agent.get(&url).header("Cookie", cookie).call(),
+        };
+
+        let mut response = response.map_err(|e| Error::Remote(format!("{}: {}", url, e)))?;
+        let status = response.status().as_u16();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| Error::Remote(format!("{}: {}", url, e)))?;
+
+        Ok((status, body))
+    }
+}
+
+/// A local HTTP server for tests. `respond` maps a method, URL, cookie and body
+/// to a status and body, and every request is recorded, along with its user agent.
+#[cfg(test)]
+pub fn mock_server(
+    respond: fn(&str, &str, &str, &str) -> (u16, String),
+) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let header = |name: &str| {
+                request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+                    .map(|h| h.value.to_string())
+                    .unwrap_or_default()
+            };
+
+            let (cookie, user_agent) = (header("Cookie"), header("User-Agent"));
+            let method = request.method().to_string();
+            let url = request.url().to_string();
+
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+
+            recorded
+                .lock()
+                .unwrap()
+                .push(format!("{} {} {}", method, url, user_agent));
+
+            let (status, response) = respond(&method, &url, &cookie, &body);
+
+            request
+                .respond(tiny_http::Response::from_string(response).with_status_code(status))
+                .unwrap();
+        }
+    });
+
+    (address, requests)
+}
+
+#[cfg(test)]
+fn input_server(_: &str, url: &str, cookie: &str, _: &str) -> (u16, String) {
+    match (url, cookie) {
+        (_, "session=expired") => (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string()),
+        ("/2023/day/1/input", _) => (200, "1abc2\npqr3stu8vwx\n".to_string()),
+        ("/2023/day/2/input", _) => (404, "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.".to_string()),
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+#[cfg(test)]
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_release_time() {
+    assert_eq!(
+        release_time(2023, 1).duration_since(UNIX_EPOCH).unwrap(),
+        Duration::from_secs(1_701_406_800)
+    );
+    assert_eq!(
+        release_time(2015, 25).duration_since(UNIX_EPOCH).unwrap(),
+        Duration::from_secs(1_451_019_600)
+    );
+}
+
+#[test]
+fn test_input_is_cached() {
+    let (url, requests) = mock_server(input_server);
+    let cache = temp_dir("fetch-cache");
+    let client = Client::new(&url, "good", &cache);
+
+    assert_eq!(
+        client.input(2023, 1),
+        Ok("1abc2\npqr3stu8vwx\n".to_string())
+    );
+    assert_eq!(
+        client.input(2023, 1),
+        Ok("1abc2\npqr3stu8vwx\n".to_string())
+    );
+    assert_eq!(
+        fs::read_to_string(cache.join("2023/day1.txt")).unwrap(),
+        "1abc2\npqr3stu8vwx\n"
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0], format!("GET /2023/day/1/input {}", USER_AGENT));
+
+    fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn test_input_errors() {
+    let (url, requests) = mock_server(input_server);
+    let cache = temp_dir("fetch-errors");
+
+    assert_eq!(
+        Client::new(&url, "good", &cache).input(2023, 3),
+        Err(Error::Remote(format!(
+            "{}/2023/day/3/input answered with status 404: 404 Not Found",
+            url
+        )))
+    );
+
+    assert_eq!(
+        Client::new(&url, "expired", &cache).input(2023, 1),
+        Err(Error::Remote(
+            "the session cookie was refused, it has probably expired".to_string()
+        ))
+    );
+
+    assert_eq!(
+        Client::new(&url, "good", &cache).input(2023, 2),
+        Err(Error::Remote(
+            "day 2 of 2023 hasn't been released yet".to_string()
+        ))
+    );
+
+    // Days that haven't been released are refused before anything is sent
+    assert_eq!(
+        Client::new(&url, "good", &cache).input(2999, 1),
+        Err(Error::Remote(
+            "day 1 of 2999 hasn't been released yet".to_string()
+        ))
+    );
+
+    assert_eq!(requests.lock().unwrap().len(), 3);
+    assert!(!cache.join("2023").exists());
+}
@@ -0,0 +1,170 @@
+//! The solvers for other languages, through the C ABI. The declarations are in
+//! `include/advent_of_code.h`, which cbindgen generates from this file.
+//!
+//! Every registered solver is reachable through [`aoc_solve`], so new days need
+//! no changes here.
+
+use std::{
+    ffi::{c_char, CString},
+    panic, slice, str,
+};
+
+use crate::registry::{self, SOLUTIONS};
+
+/// One part of one day's puzzle, like 2023/5/2.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AocPuzzle {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+}
+
+/// The answer to a puzzle, or the reason there isn't one.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocAnswer {
+    /// Whether `text` is the answer rather than an error message.
+    pub ok: bool,
+    /// A NUL-terminated string owned by the library. Release it with `aoc_answer_free`.
+    pub text: *mut c_char,
+}
+
+impl AocAnswer {
+    fn new(answer: Result<String, String>) -> Self {
+        let (ok, text) = match answer {
+            Ok(text) => (true, text),
+            Err(text) => (false, text),
+        };
+
+        // Error messages can quote the input, which may contain NULs
+        let text = CString::new(text.replace('\0', "\\0")).unwrap();
+
+        AocAnswer {
+            ok,
+            text: text.into_raw(),
+        }
+    }
+}
+
+/// How many puzzles have a solver.
+#[no_mangle]
+pub extern "C" fn aoc_puzzle_count() -> usize {
+    SOLUTIONS.len()
+}
+
+/// The puzzle at `index`, ordered by year, day and part. Every field is 0 past the last one.
+#[no_mangle]
+pub extern "C" fn aoc_puzzle(index: usize) -> AocPuzzle {
+    match SOLUTIONS.get(index) {
+        Some(s) => AocPuzzle {
+            year: s.year,
+            day: s.day,
+            part: s.part,
+        },
+        None => AocPuzzle {
+            year: 0,
+            day: 0,
+            part: 0,
+        },
+    }
+}
+
+/// Solves a puzzle for the `len` bytes of UTF-8 at `input`.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or may be null when `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(puzzle: AocPuzzle, input: *const u8, len: usize) -> AocAnswer {
+    let input = match input.is_null() {
+        true if len == 0 => &[],
+        true => return AocAnswer::new(Err("the input is null".to_string())),
+        false => slice::from_raw_parts(input, len),
+    };
+
+    AocAnswer::new(solve(puzzle, input))
+}
+
+fn solve(puzzle: AocPuzzle, input: &[u8]) -> Result<String, String> {
+    let AocPuzzle { year, day, part } = puzzle;
+    let solution = registry::find(year, day, part)
+        .ok_or_else(|| format!("{}/{}/{} has no solver", year, day, part))?;
+    let input = str::from_utf8(input).map_err(|e| format!("the input is not UTF-8: {}", e))?;
+
+    // Unwinding into C is undefined, so a panic becomes an error instead
+    match panic::catch_unwind(|| solution.solve(input)) {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(_) => Err(format!("the solver for {} panicked", solution)),
+    }
+}
+
+/// Releases the text of an answer from `aoc_solve`. Each answer must be freed once.
+///
+/// # Safety
+///
+/// `answer` must come from `aoc_solve` and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_answer_free(answer: AocAnswer) {
+    if !answer.text.is_null() {
+        drop(CString::from_raw(answer.text));
+    }
+}
+
+#[cfg(test)]
+fn solve_str(year: u16, day: u8, part: u8, input: &[u8]) -> (bool, String) {
+    let answer = unsafe { aoc_solve(AocPuzzle { year, day, part }, input.as_ptr(), input.len()) };
+    let result = (answer.ok, unsafe {
+        std::ffi::CStr::from_ptr(answer.text)
+            .to_str()
+            .unwrap()
+            .to_string()
+    });
+
+    unsafe { aoc_answer_free(answer) };
+    result
+}
+
+#[test]
+fn test_aoc_solve() {
+    let example = b"Time:      7  15   30\nDistance:  9  40  200\n";
+
+    assert_eq!(solve_str(2023, 6, 1, example), (true, "288".to_string()));
+    assert_eq!(solve_str(2023, 6, 2, example), (true, "71503".to_string()));
+    assert_eq!(
+        solve_str(2023, 25, 1, example),
+        (false, "2023/25/1 has no solver".to_string())
+    );
+    assert_eq!(
+        solve_str(2023, 6, 1, b"Time: \xff"),
+        (
+            false,
+            "the input is not UTF-8: invalid utf-8 sequence of 1 bytes from index 6".to_string()
+        )
+    );
+    assert_eq!(
+        solve_str(2023, 3, 1, b"..\0."),
+        (
+            false,
+            "line 1, column 3: unexpected character '\\0'".to_string()
+        )
+    );
+
+    let empty = unsafe { aoc_solve(aoc_puzzle(0), std::ptr::null(), 0) };
+    assert!(!empty.ok);
+    unsafe { aoc_answer_free(empty) };
+}
+
+#[test]
+fn test_aoc_puzzle() {
+    assert_eq!(aoc_puzzle_count(), SOLUTIONS.len());
+    assert_eq!(
+        aoc_puzzle(0),
+        AocPuzzle {
+            year: 2023,
+            day: 1,
+            part: 1
+        }
+    );
+    assert_eq!(aoc_puzzle(SOLUTIONS.len()).year, 0);
+}
@@ -0,0 +1,228 @@
+//! Random puzzle inputs at any scale, for benchmarking and for feeding the
+//! property and fuzz tests. The same seed and settings always give the same input.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{Error, Result};
+
+pub mod y2023;
+
+/// SplitMix64. Small, fast and fully specified, so a seed gives the same
+/// input on every platform and with every version of every dependency.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, which must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        // Lemire's multiply and shift. The bias is at most n / 2^64, far too small to matter here.
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number between `low` and `high`, both included.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high as i128 - low as i128 + 1) as u128;
+
+        match u64::try_from(span) {
+            Ok(span) => (low as i128 + self.below(span) as i128) as i64,
+            // Every i64 is allowed
+            Err(_) => self.next_u64() as i64,
+        }
+    }
+
+    /// True `numerator` times out of `denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// A number that shapes a generated input, like the width of a schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+    pub name: &'static str,
+    pub default: u64,
+    pub description: &'static str,
+}
+
+/// The value of every setting of one generator, defaults included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings(BTreeMap<&'static str, u64>);
+
+impl Settings {
+    /// The value of a setting the generator declared.
+    pub fn get(&self, name: &str) -> u64 {
+        self.0[name]
+    }
+
+    /// Like [`Settings::get`], but errors unless the value is between `low` and `high`.
+    pub fn get_between(&self, name: &str, low: u64, high: u64) -> Result<u64> {
+        match self.get(name) {
+            value if (low..=high).contains(&value) => Ok(value),
+            value => Err(Error::Invalid(format!(
+                "{} must be between {} and {}, not {}",
+                name, low, high, value
+            ))),
+        }
+    }
+}
+
+/// Creates inputs for one day.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    pub settings: &'static [Setting],
+    generate: fn(&mut Rng, &Settings) -> Result<String>,
+}
+
+impl Generator {
+    /// Generates an input from `seed`, with `overrides` replacing the default settings.
+    pub fn generate<'a>(
+        &self,
+        seed: u64,
+        overrides: impl IntoIterator<Item = (&'a str, u64)>,
+    ) -> Result<String> {
+        let mut settings = self
+            .settings
+            .iter()
+            .map(|s| (s.name, s.default))
+            .collect::<BTreeMap<_, _>>();
+
+        for (name, value) in overrides {
+            match settings.get_mut(name) {
+                Some(setting) => *setting = value,
+                None => {
+                    return Err(Error::Invalid(format!(
+                        "{} has no setting {:?}, only {}",
+                        self,
+                        name,
+                        self.settings
+                            .iter()
+                            .map(|s| s.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )))
+                }
+            }
+        }
+
+        (self.generate)(&mut Rng::new(seed), &Settings(settings))
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+macro_rules! generator {
+    ($year:literal, $day:literal, $settings:path, $generate:path) => {
+        Generator {
+            year: $year,
+            day: $day,
+            settings: $settings,
+            generate: $generate,
+        }
+    };
+}
+
+/// Every generator, ordered by year and day.
+pub const GENERATORS: &[Generator] = &[
+    generator!(2023, 1, y2023::DAY1_SETTINGS, y2023::day1),
+    generator!(2023, 2, y2023::DAY2_SETTINGS, y2023::day2),
+    generator!(2023, 3, y2023::DAY3_SETTINGS, y2023::day3),
+    generator!(2023, 4, y2023::DAY4_SETTINGS, y2023::day4),
+    generator!(2023, 5, y2023::DAY5_SETTINGS, y2023::day5),
+    generator!(2023, 6, y2023::DAY6_SETTINGS, y2023::day6),
+    generator!(2023, 7, y2023::DAY7_SETTINGS, y2023::day7),
+    generator!(2023, 8, y2023::DAY8_SETTINGS, y2023::day8),
+    generator!(2023, 9, y2023::DAY9_SETTINGS, y2023::day9),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.year == year && g.day == day)
+}
+
+/// Generates an input for a day, for tests that want one at a particular scale.
+#[cfg(test)]
+pub fn generate(year: u16, day: u8, seed: u64, settings: &[(&str, u64)]) -> String {
+    find(year, day)
+        .unwrap()
+        .generate(seed, settings.iter().copied())
+        .unwrap()
+}
+
+#[test]
+fn test_rng() {
+    // The first outputs for seed 0 from the reference implementation
+    let mut rng = Rng::new(0);
+
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+
+    let mut rng = Rng::new(7);
+
+    assert!((0..1000).all(|_| rng.below(10) < 10));
+    assert!((0..1000).all(|_| (-3..=3).contains(&rng.between(-3, 3))));
+    assert!((0..1000).any(|_| rng.between(-3, 3) == -3));
+    assert!((0..1000).any(|_| rng.between(-3, 3) == 3));
+
+    let mut items = (0..10).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_generate() {
+    for generator in GENERATORS {
+        let input = generator.generate(1, []).unwrap();
+
+        assert_eq!(generator.generate(1, []).unwrap(), input);
+        assert_ne!(generator.generate(2, []).unwrap(), input);
+
+        // Every generated input has answers for both parts
+        for solution in crate::registry::SOLUTIONS
+            .iter()
+            .filter(|s| (s.year, s.day) == (generator.year, generator.day))
+        {
+            assert!(
+                solution.solve(&input).is_ok(),
+                "{} failed on seed 1: {:?}",
+                solution,
+                solution.solve(&input)
+            );
+        }
+    }
+
+    assert_eq!(
+        find(2023, 3).unwrap().generate(1, [("depth", 3)]),
+        Err(Error::Invalid(
+            "2023/3 has no setting \"depth\", only width, height".to_string()
+        ))
+    );
+}
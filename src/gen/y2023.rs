@@ -0,0 +1,578 @@
+use crate::{
+    gen::{Rng, Setting, Settings},
+    math::is_prime,
+    Error, Result,
+};
+
+macro_rules! settings {
+    ($($name:literal = $default:literal: $description:literal,)*) => {
+        &[$(Setting {
+            name: $name,
+            default: $default,
+            description: $description,
+        }),*]
+    };
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub const DAY1_SETTINGS: &[Setting] = settings! {
+    "lines" = 1000: "how many lines the document has",
+    "length" = 40: "the most characters on a line",
+};
+
+/// Lines of letters with digits, written out or not, scattered through them.
+pub fn day1(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let lines = settings.get_between("lines", 1, u32::MAX as u64)?;
+    let length = settings.get_between("length", 1, u32::MAX as u64)? as usize;
+
+    let mut input = String::new();
+
+    for _ in 0..lines {
+        let mut line = String::new();
+        let target = rng.below(length as u64) as usize + 1;
+
+        while line.len() < target {
+            match rng.below(8) {
+                0 => line.push((b'1' + rng.below(9) as u8) as char),
+                1 => line.push_str(DIGIT_WORDS[rng.below(9) as usize]),
+                _ => line.push((b'a' + rng.below(26) as u8) as char),
+            }
+        }
+
+        line.truncate(target);
+
+        // Every line needs at least one digit for part 1
+        if !line.bytes().any(|b| b.is_ascii_digit()) {
+            let at = rng.below(line.len() as u64) as usize;
+            line.replace_range(at..=at, &(rng.below(9) + 1).to_string());
+        }
+
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    Ok(input)
+}
+
+pub const DAY2_SETTINGS: &[Setting] = settings! {
+    "games" = 100: "how many games are played",
+    "draws" = 6: "the most handfuls drawn in one game",
+    "cubes" = 20: "the most cubes of one color in a handful",
+};
+
+/// Games where each handful shows some of the three colors in any order.
+pub fn day2(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let games = settings.get_between("games", 1, u32::MAX as u64)?;
+    let draws = settings.get_between("draws", 1, u32::MAX as u64)?;
+    let cubes = settings.get_between("cubes", 1, u32::MAX as u64)?;
+
+    let mut input = String::new();
+
+    for game in 1..=games {
+        let handfuls = (0..rng.below(draws) + 1)
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+
+                colors[..rng.below(3) as usize + 1]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.below(cubes) + 1, color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+
+        input.push_str(&format!("Game {}: {}\n", game, handfuls.join("; ")));
+    }
+
+    Ok(input)
+}
+
+pub const DAY3_SETTINGS: &[Setting] = settings! {
+    "width" = 140: "how many cells each row has",
+    "height" = 140: "how many rows the schematic has",
+};
+
+/// A schematic of part numbers up to three digits long and scattered symbols.
+pub fn day3(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+    let width = settings.get_between("width", 1, u32::MAX as u64)? as usize;
+    let height = settings.get_between("height", 1, u32::MAX as u64)?;
+
+    let mut input = String::new();
+
+    for _ in 0..height {
+        let mut row = Vec::with_capacity(width + 1);
+
+        while row.len() < width {
+            let digits = (rng.below(3) as usize + 1).min(width - row.len());
+
+            if rng.chance(1, 6) {
+                row.push(b'1' + rng.below(9) as u8);
+                row.extend((1..digits).map(|_| b'0' + rng.below(10) as u8));
+
+                // Keep the next number from running into this one
+                if row.len() < width {
+                    row.push(b'.');
+                }
+            } else if rng.chance(1, 10) {
+                row.push(*rng.pick(SYMBOLS));
+            } else {
+                row.push(b'.');
+            }
+        }
+
+        row.push(b'\n');
+        input.push_str(std::str::from_utf8(&row).unwrap());
+    }
+
+    Ok(input)
+}
+
+pub const DAY4_SETTINGS: &[Setting] = settings! {
+    "cards" = 200: "how many cards there are",
+    "winning" = 10: "how many winning numbers each card has",
+    "numbers" = 25: "how many numbers each card has",
+    "copies" = 1_000_000: "the most copies of one card that part 2 can end up with",
+};
+
+/// Cards with numbers up to 99. No card wins copies of cards past the end of
+/// the table, and cards win fewer numbers where copies would pass the limit.
+pub fn day4(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let cards = settings.get_between("cards", 1, u32::MAX as u64)?;
+    let winning = settings.get_between("winning", 1, 98)? as usize;
+    let numbers = settings.get_between("numbers", 1, 99 - winning as u64)? as usize;
+    let limit = settings.get_between("copies", 1, u32::MAX as u64)?;
+
+    // How many of each card there will be, so far
+    let mut copies = vec![1; cards as usize];
+
+    let id_width = cards.to_string().len();
+    let mut input = String::new();
+
+    for id in 1..=cards {
+        let mut pool = (1..=99u8).collect::<Vec<_>>();
+        rng.shuffle(&mut pool);
+
+        let (winners, others) = pool.split_at(winning);
+        let idx = id as usize - 1;
+        let most = winning.min(numbers).min(copies.len() - id as usize);
+        let mut matches = rng.below(most as u64 + 1) as usize;
+
+        while copies[idx + 1..=idx + matches]
+            .iter()
+            .any(|&n| n + copies[idx] > limit)
+        {
+            matches -= 1;
+        }
+
+        for i in idx + 1..=idx + matches {
+            copies[i] += copies[idx];
+        }
+
+        let mut ours = winners[..matches].to_vec();
+        ours.extend_from_slice(&others[..numbers - matches]);
+        rng.shuffle(&mut ours);
+
+        let list = |numbers: &[u8]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        input.push_str(&format!(
+            "Card {:>width$}: {} | {}\n",
+            id,
+            list(winners),
+            list(&ours),
+            width = id_width
+        ));
+    }
+
+    Ok(input)
+}
+
+pub const DAY5_SETTINGS: &[Setting] = settings! {
+    "seeds" = 10: "how many seed ranges there are",
+    "maps" = 7: "how many maps lead from seeds to locations",
+    "ranges" = 30: "how many ranges each map has, which may overlap",
+    "max" = 4_294_967_296: "the numbers stay below this",
+};
+
+/// An almanac whose ranges overlap, so the first listed range has to win.
+pub fn day5(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    const STAGES: [&str; 6] = [
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+    ];
+
+    let seeds = settings.get_between("seeds", 1, u32::MAX as u64)?;
+    let maps = settings.get_between("maps", 1, u32::MAX as u64)? as usize;
+    let ranges = settings.get_between("ranges", 0, u32::MAX as u64)?;
+    let max = settings.get_between("max", 2, u64::MAX)?;
+
+    let seeds = (0..seeds)
+        .map(|_| {
+            let start = rng.below(max - 1);
+            let len = rng.below((max - start).min(max / 10 + 1)) + 1;
+
+            format!("{} {}", start, len)
+        })
+        .collect::<Vec<_>>();
+
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    let stage = |i: usize| match i {
+        0 => "seed".to_string(),
+        i if i == maps => "location".to_string(),
+        i if maps <= STAGES.len() + 1 => STAGES[i - 1].to_string(),
+        i => format!("stage{}", i),
+    };
+
+    for map in 0..maps {
+        input.push_str(&format!("\n{}-to-{} map:\n", stage(map), stage(map + 1)));
+
+        for _ in 0..ranges {
+            let len = rng.below(max / ranges.max(2)) + 1;
+            let src = rng.below(max - len + 1);
+            let dst = rng.below(max - len + 1);
+
+            input.push_str(&format!("{} {} {}\n", dst, src, len));
+        }
+    }
+
+    Ok(input)
+}
+
+pub const DAY6_SETTINGS: &[Setting] = settings! {
+    "races" = 4: "how many races there are",
+    "time" = 100: "the longest a race lasts",
+};
+
+/// Races that can all be won. Part 2 reads every race as one long race, so
+/// with many races or long ones its numbers stop fitting in a `u64`.
+pub fn day6(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let races = settings.get_between("races", 1, u32::MAX as u64)?;
+    let time = settings.get_between("time", 2, u32::MAX as u64)?;
+
+    let (times, distances): (Vec<_>, Vec<_>) = (0..races)
+        .map(|_| {
+            let time = rng.below(time - 1) + 2;
+            // Holding for less than half the race never reaches the best distance
+            let hold = rng.below(time / 2);
+
+            (time, hold * (time - hold))
+        })
+        .unzip();
+
+    let width = times
+        .iter()
+        .chain(&distances)
+        .max()
+        .unwrap()
+        .to_string()
+        .len()
+        + 2;
+    let row = |numbers: &[u64]| {
+        numbers
+            .iter()
+            .map(|n| format!("{:>width$}", n, width = width))
+            .collect::<String>()
+    };
+
+    Ok(format!(
+        "Time:    {}\nDistance:{}\n",
+        row(&times),
+        row(&distances)
+    ))
+}
+
+pub const DAY7_SETTINGS: &[Setting] = settings! {
+    "hands" = 1000: "how many hands there are",
+    "bid" = 1000: "the largest bid",
+};
+
+/// Hands drawn from a few ranks each, so every kind of hand turns up.
+pub fn day7(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let hands = settings.get_between("hands", 1, u32::MAX as u64)?;
+    let bid = settings.get_between("bid", 1, u32::MAX as u64)?;
+
+    let mut input = String::new();
+
+    for _ in 0..hands {
+        let mut ranks = b"AKQJT98765432".to_vec();
+        rng.shuffle(&mut ranks);
+        ranks.truncate(rng.below(5) as usize + 1);
+
+        let hand = (0..5)
+            .map(|_| *rng.pick(&ranks) as char)
+            .collect::<String>();
+
+        input.push_str(&format!("{} {}\n", hand, rng.below(bid) + 1));
+    }
+
+    Ok(input)
+}
+
+pub const DAY8_SETTINGS: &[Setting] = settings! {
+    "ghosts" = 6: "how many ghosts walk at once",
+    "instructions" = 263: "how many left and right instructions there are",
+    "factor" = 7: "every cycle's length is this times a different prime",
+    "primes" = 60: "the largest prime that a cycle's length is made from",
+    "tail" = 0: "how many steps every ghost takes before it starts looping",
+};
+
+/// Three-character names for nodes that neither start nor end a ghost's walk.
+const NAME_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// The characters that leave a name neither a start nor an exit.
+const MIDDLE_CHARS: &[u8] = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+
+fn node_name(idx: usize) -> String {
+    let middle = MIDDLE_CHARS.len();
+
+    [
+        NAME_CHARS[idx / middle / NAME_CHARS.len()],
+        NAME_CHARS[idx / middle % NAME_CHARS.len()],
+        MIDDLE_CHARS[idx % middle],
+    ]
+    .iter()
+    .map(|&b| b as char)
+    .collect()
+}
+
+/// The start or exit of a ghost's walk, where the first ghost walks from AAA to ZZZ.
+fn end_name(ghost: usize, last: char) -> String {
+    match ghost {
+        0 => last.to_string().repeat(3),
+        g => format!(
+            "{}{}{}",
+            MIDDLE_CHARS[g / MIDDLE_CHARS.len()] as char,
+            MIDDLE_CHARS[g % MIDDLE_CHARS.len()] as char,
+            last
+        ),
+    }
+}
+
+/// A network where every ghost walks a tail and then a cycle whose only exit
+/// is at its end. Cycle lengths are `factor` times distinct primes, so the
+/// answer to part 2 is `tail` plus their least common multiple.
+///
+/// Every step along a cycle has two nodes, and the instructions pick which one
+/// the ghost lands on, but either way it moves one step further along.
+pub fn day8(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let ghosts = settings.get_between("ghosts", 1, 1000)? as usize;
+    let instructions = settings.get_between("instructions", 1, u32::MAX as u64)?;
+    let factor = settings.get_between("factor", 1, u32::MAX as u64)?;
+    let largest = settings.get_between("primes", 2, u32::MAX as u64)?;
+    let tail = settings.get_between("tail", 0, u32::MAX as u64)? as usize;
+
+    let mut primes = (2..=largest).filter(|&n| is_prime(n)).collect::<Vec<_>>();
+
+    if primes.len() < ghosts {
+        return Err(Error::Invalid(format!(
+            "there are only {} primes up to {}, too few for {} ghosts",
+            primes.len(),
+            largest,
+            ghosts
+        )));
+    }
+
+    rng.shuffle(&mut primes);
+
+    let cycles = primes[..ghosts]
+        .iter()
+        .map(|&p| (p * factor) as usize)
+        .collect::<Vec<_>>();
+
+    let capacity = NAME_CHARS.len() * NAME_CHARS.len() * MIDDLE_CHARS.len();
+    let needed = cycles.iter().map(|&c| 2 * (tail + c)).sum::<usize>();
+
+    if needed > capacity {
+        return Err(Error::Invalid(format!(
+            "the network needs {} nodes, but only {} fit in three-character names",
+            needed, capacity
+        )));
+    }
+
+    let mut names = (0..capacity).collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    let mut names = names.into_iter().map(node_name);
+
+    let mut nodes = Vec::new();
+
+    for (ghost, &cycle) in cycles.iter().enumerate() {
+        // Each step of the walk, from the start through the tail and once around
+        // the cycle, as a left and a right node. The start and exit are single nodes.
+        let steps = (0..=tail + cycle)
+            .map(|step| match step {
+                0 => [end_name(ghost, 'A'), end_name(ghost, 'A')],
+                step if step == tail + cycle => [end_name(ghost, 'Z'), end_name(ghost, 'Z')],
+                _ => [names.next().unwrap(), names.next().unwrap()],
+            })
+            .collect::<Vec<_>>();
+
+        for (step, pair) in steps.iter().enumerate() {
+            // The exit leads back to the first step of the cycle
+            let next = match step + 1 {
+                next if next < steps.len() => &steps[next],
+                _ => &steps[tail + 1],
+            };
+
+            for node in pair.iter().take(if pair[0] == pair[1] { 1 } else { 2 }) {
+                nodes.push(format!("{} = ({}, {})", node, next[0], next[1]));
+            }
+        }
+    }
+
+    rng.shuffle(&mut nodes);
+
+    let instructions = (0..instructions)
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect::<String>();
+
+    Ok(format!("{}\n\n{}\n", instructions, nodes.join("\n")))
+}
+
+pub const DAY9_SETTINGS: &[Setting] = settings! {
+    "lines" = 200: "how many sequences there are",
+    "length" = 21: "how many values each sequence has",
+    "degree" = 6: "the degree of the polynomial behind every sequence",
+};
+
+/// Sequences that follow a polynomial of exactly the given degree.
+pub fn day9(rng: &mut Rng, settings: &Settings) -> Result<String> {
+    let lines = settings.get_between("lines", 1, u32::MAX as u64)?;
+    let length = settings.get_between("length", 1, u32::MAX as u64)?;
+    let degree = settings.get_between("degree", 0, length - 1)? as usize;
+
+    let too_large =
+        || Error::Invalid("the values don't fit in an i64, try a lower degree".to_string());
+
+    let mut input = String::new();
+
+    for _ in 0..lines {
+        // The polynomial in Newton's form, one difference at index 0 for each degree
+        let mut differences = (0..=degree)
+            .map(|k| {
+                if k == 0 {
+                    rng.between(-20, 20)
+                } else {
+                    rng.between(-5, 5)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if degree > 0 && differences[degree] == 0 {
+            differences[degree] = if rng.chance(1, 2) { 1 } else { -1 };
+        }
+
+        let values = (0..length as i128)
+            .map(|n| {
+                let mut value = 0i128;
+                let mut binomial = 1i128;
+
+                for (k, &difference) in differences.iter().enumerate() {
+                    if k > 0 {
+                        binomial = binomial
+                            .checked_mul(n - k as i128 + 1)
+                            .ok_or_else(too_large)?
+                            / k as i128;
+                    }
+
+                    value = (difference as i128)
+                        .checked_mul(binomial)
+                        .and_then(|term| value.checked_add(term))
+                        .ok_or_else(too_large)?;
+                }
+
+                i64::try_from(value).map_err(|_| too_large())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let values = values.iter().map(i64::to_string).collect::<Vec<_>>();
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+
+    Ok(input)
+}
+
+#[test]
+fn test_day8() {
+    use crate::{gen::generate, math::lcm, y2023::day8};
+
+    for (seed, tail, factor) in [(1, 0, 7), (2, 3, 1), (3, 0, 263)] {
+        let settings = [
+            ("ghosts", 4),
+            ("instructions", 11),
+            ("factor", factor),
+            ("primes", 40),
+            ("tail", tail),
+        ];
+
+        let network = day8::generator(&generate(2023, 8, seed, &settings)).unwrap();
+
+        // The cycles come from the same shuffled primes the generator picked
+        let mut rng = Rng::new(seed);
+        let mut primes = (2..=40u64).filter(|&n| is_prime(n)).collect::<Vec<_>>();
+        rng.shuffle(&mut primes);
+
+        let cycles = primes[..4]
+            .iter()
+            .map(|&p| (p * factor) as u128)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            day8::part1(&network),
+            Ok((tail as u128 + cycles[0]) as usize)
+        );
+        assert_eq!(
+            day8::part2(&network).ok(),
+            cycles
+                .iter()
+                .try_fold(1, |total, &c| lcm(total, c))
+                .map(|steps| tail as u128 + steps)
+        );
+    }
+
+    assert_eq!(
+        crate::gen::find(2023, 8)
+            .unwrap()
+            .generate(1, [("ghosts", 20)]),
+        Err(Error::Invalid(
+            "there are only 17 primes up to 60, too few for 20 ghosts".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_day9() {
+    use crate::y2023::day9::Sequence;
+
+    let input = crate::gen::generate(2023, 9, 1, &[("lines", 20), ("degree", 4)]);
+
+    for line in input.lines() {
+        let values = line
+            .split(' ')
+            .map(|n| n.parse().unwrap())
+            .collect::<Vec<i64>>();
+
+        assert_eq!(values.len(), 21);
+        assert_eq!(Sequence::new(&values).unwrap().degree(), 4);
+    }
+
+    assert!(crate::gen::find(2023, 9)
+        .unwrap()
+        .generate(1, [("degree", 20), ("length", 1000)])
+        .is_err());
+}
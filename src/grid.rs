@@ -0,0 +1,499 @@
+use std::{
+    fmt,
+    ops::{Add, Index, IndexMut, Sub},
+};
+
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The four points directly above, right of, below and left of this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The eight points surrounding this one, diagonals included.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A compass direction, with north pointing towards the first row of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    fn from_index(idx: usize) -> Self {
+        Direction::ALL[idx % 8]
+    }
+
+    fn index(self) -> usize {
+        Direction::ALL.iter().position(|&d| d == self).unwrap()
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::from_index(self.index() + 2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::from_index(self.index() + 6)
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::from_index(self.index() + 4)
+    }
+}
+
+/// A rectangular grid, stored row by row in a single `Vec`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from cells listed row by row, or `None` if they don't fill `width` columns evenly.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Grid {
+                width,
+                height: 0,
+                cells,
+            });
+        }
+
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, turning every character into a cell through `f`.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    Error::parse(y + 1, x + 1, format!("unexpected character {:?}", c))
+                })?;
+
+                cells.push(cell);
+            }
+
+            let row_width = cells.len() - before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(Error::parse(
+                        y + 1,
+                        width.min(row_width) + 1,
+                        format!("expected {} cells, found {}", width, row_width),
+                    ));
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as isize).contains(&point.x) && (0..self.height as isize).contains(&point.y)
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+
+        (0..self.height as isize)
+            .flat_map(move |y| (0..width as isize).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell in the grid along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The in-bounds cells directly above, right of, below and left of `point`.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbours()
+            .filter_map(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// The in-bounds cells surrounding `point`, diagonals included.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point
+            .neighbours8()
+            .filter_map(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Every `width` by `height` sub-grid, ordered by its top-left corner.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = Grid<&T>> {
+        let xs = (self.width + 1).saturating_sub(width);
+        let ys = (self.height + 1).saturating_sub(height);
+
+        (0..ys).flat_map(move |y0| {
+            (0..xs).map(move |x0| {
+                let cells = (y0..y0 + height)
+                    .flat_map(|y| &self.row(y)[x0..x0 + width])
+                    .collect();
+
+                Grid {
+                    width,
+                    height,
+                    cells,
+                }
+            })
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a new grid of the given size, taking each cell from the point `f` maps it to.
+    fn remap(&self, width: usize, height: usize, f: impl Fn(usize, usize) -> (usize, usize)) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = f(x, y);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+
+        self.remap(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+
+        self.remap(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+
+        self.remap(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+
+        self.remap(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {} is outside the grid", point))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for c in row {
+                write!(f, "{}", c)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn chars(input: &str) -> Grid<char> {
+    Grid::parse(input, Some).unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let grid = chars("ab\ncd\nef");
+
+    assert_eq!(grid.width(), 2);
+    assert_eq!(grid.height(), 3);
+    assert_eq!(grid[Point::new(1, 2)], 'f');
+    assert_eq!(grid.get(Point::new(2, 0)), None);
+    assert_eq!(grid.get(Point::new(-1, 0)), None);
+
+    assert_eq!(
+        Grid::parse("ab\nc", Some).err(),
+        Some(Error::parse(2, 2, "expected 2 cells, found 1"))
+    );
+
+    assert_eq!(
+        Grid::parse("ab\n#d", |c| c.is_alphabetic().then_some(c)).err(),
+        Some(Error::parse(2, 1, "unexpected character '#'"))
+    );
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = chars("abc\ndef\nghi");
+
+    let around = |p| grid.neighbours(p).map(|(_, c)| *c).collect::<String>();
+    let around8 = |p| grid.neighbours8(p).map(|(_, c)| *c).collect::<String>();
+
+    assert_eq!(around(Point::new(1, 1)), "bfhd");
+    assert_eq!(around(Point::new(0, 0)), "bd");
+    assert_eq!(around8(Point::new(1, 1)), "bcfihgda");
+    assert_eq!(around8(Point::new(2, 2)), "fhe");
+}
+
+#[test]
+fn test_rows_columns_windows() {
+    let grid = chars("abc\ndef");
+
+    assert_eq!(
+        grid.rows().collect::<Vec<_>>(),
+        [['a', 'b', 'c'], ['d', 'e', 'f']]
+    );
+    assert_eq!(
+        grid.columns()
+            .map(|col| col.collect::<String>())
+            .collect::<Vec<_>>(),
+        ["ad", "be", "cf"]
+    );
+    assert_eq!(
+        grid.windows(2, 2)
+            .map(|w| w.map(|c| **c).to_string())
+            .collect::<Vec<_>>(),
+        ["ab\nde", "bc\nef"]
+    );
+}
+
+#[test]
+fn test_transformations() {
+    let grid = chars("abc\ndef");
+
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+    assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+    assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+    assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+    assert_eq!(
+        grid.rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise(),
+        grid
+    );
+}
+
+#[test]
+fn test_directions() {
+    assert_eq!(Direction::North.turn_right(), Direction::East);
+    assert_eq!(Direction::North.turn_left(), Direction::West);
+    assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+    assert_eq!(
+        Point::new(2, 3).step(Direction::SouthWest),
+        Point::new(1, 4)
+    );
+    assert_eq!(Point::new(2, 3).manhattan_distance(Point::new(-1, 5)), 5);
+}
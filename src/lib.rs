@@ -1,16 +1,36 @@
+#[cfg(feature = "runner")]
 extern crate aoc_runner;
 
+#[cfg(feature = "runner")]
 #[macro_use]
 extern crate aoc_runner_derive;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+pub mod answers;
+#[cfg(feature = "runner")]
+pub mod batch;
+#[cfg(feature = "runner")]
+pub mod cli;
+pub mod error;
+#[cfg(feature = "runner")]
+pub mod fetch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ffi;
+pub mod gen;
+pub mod grid;
+pub mod math;
+pub mod memory;
+pub mod parse;
+pub mod registry;
+#[cfg(feature = "runner")]
+pub mod scaffold;
+#[cfg(feature = "runner")]
+pub mod submit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub mod y2023;
+
+pub use error::{Error, Result};
+
+#[cfg(feature = "runner")]
 aoc_lib! { year = 2023 }
@@ -1,3 +1,22 @@
-use aoc_runner_derive::aoc_main;
+use std::{env, io, process::ExitCode};
 
-aoc_main! { lib = advent_of_code }
+use advent_of_code::cli;
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match cli::execute(&command, &mut io::stdout().lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
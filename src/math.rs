@@ -0,0 +1,462 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+/// The primitive integer types the functions in this module work with.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn rem_euclid(self, other: Self) -> Self;
+    fn abs(self) -> Self;
+    /// The value as a `u128`, or `None` if it's negative.
+    fn to_u128(self) -> Option<u128>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty: |$n:ident| $abs:expr),* $(,)?) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn rem_euclid(self, other: Self) -> Self {
+                    <$t>::rem_euclid(self, other)
+                }
+
+                fn abs(self) -> Self {
+                    let $n = self;
+                    $abs
+                }
+
+                fn to_u128(self) -> Option<u128> {
+                    u128::try_from(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer! {
+    u32: |n| n,
+    u64: |n| n,
+    u128: |n| n,
+    i64: |n| n.abs(),
+    i128: |n| n.abs(),
+}
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// The least common multiple, or `None` if it doesn't fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    (a.abs() / gcd(a, b)).checked_mul(b.abs())
+}
+
+/// Finds `(g, x, y)` such that `a * x + b * y = g`, where `g` is `gcd(a, b)`.
+pub fn extended_gcd<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Adds two numbers already reduced modulo `m` without overflowing.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Subtracts two numbers already reduced modulo `m` without overflowing.
+fn sub_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+/// `a * b (mod m)`, falling back to double-and-add when the product overflows.
+/// `None` when `m` is zero.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> Option<T> {
+    if m == T::ZERO {
+        return None;
+    }
+
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product % m);
+    }
+
+    let mut result = T::ZERO;
+
+    while b > T::ZERO {
+        if b % T::TWO == T::ONE {
+            result = add_mod(result, a, m);
+        }
+
+        a = add_mod(a, a, m);
+        b = b / T::TWO;
+    }
+
+    Some(result)
+}
+
+/// `base ^ exp (mod m)` by repeated squaring, or `None` when `m` is zero. A
+/// negative exponent counts as zero.
+pub fn mod_pow<T: Integer>(base: T, mut exp: T, m: T) -> Option<T> {
+    if m == T::ZERO {
+        return None;
+    }
+
+    let mut base = base.rem_euclid(m);
+    let mut result = T::ONE % m;
+
+    while exp > T::ZERO {
+        if exp % T::TWO == T::ONE {
+            result = mul_mod(result, base, m)?;
+        }
+
+        base = mul_mod(base, base, m)?;
+        exp = exp / T::TWO;
+    }
+
+    Some(result)
+}
+
+/// Finds `x` such that `a * x = 1 (mod m)`, or `None` if `a` and `m` aren't coprime.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    if m <= T::ONE {
+        return (m == T::ONE).then_some(T::ZERO);
+    }
+
+    // The extended Euclidean algorithm, keeping only the coefficients of `a`,
+    // reduced modulo `m` so that unsigned types work too
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r % r);
+        (old_s, s) = (s, sub_mod(old_s, mul_mod(q, s, m)?, m));
+    }
+
+    (old_r == T::ONE).then_some(old_s)
+}
+
+/// The largest `r` such that `r * r <= n`, or `None` if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        return None;
+    }
+
+    if n < T::TWO {
+        return Some(n);
+    }
+
+    // Newton's method, starting above the root so the guesses only go down
+    let mut x = n / T::TWO + T::ONE;
+    let mut y = (x + n / x) / T::TWO;
+
+    while y < x {
+        x = y;
+        y = (x + n / x) / T::TWO;
+    }
+
+    Some(x)
+}
+
+/// Combines `x = a (mod m)` and `x = b (mod n)` into a single congruence
+/// `x = c (mod lcm(m, n))`, returned as `(c, lcm(m, n))`. The moduli don't need
+/// to be coprime. Returns `None` if the two contradict each other, if either
+/// modulus isn't positive, or if the combined modulus doesn't fit in `T`.
+pub fn crt<T: Integer>(a: T, m: T, b: T, n: T) -> Option<(T, T)> {
+    if m <= T::ZERO || n <= T::ZERO {
+        return None;
+    }
+
+    let (a, b) = (a.rem_euclid(m), b.rem_euclid(n));
+    let g = gcd(m, n);
+
+    if a % g != b % g {
+        return None;
+    }
+
+    // x = a + m * k, where (m / g) * k = (b - a) / g (mod n / g)
+    let modulus = lcm(m, n)?;
+    let n_g = n / g;
+    let diff = sub_mod(b, a % n, n) / g;
+    let k = mul_mod(diff, mod_inverse(m / g, n_g)?, n_g)?;
+
+    // k < n / g, so this stays below the combined modulus
+    Some((a + m * k, modulus))
+}
+
+/// Tests for primality with Miller-Rabin. The fixed set of bases makes this
+/// exact for every `n` below 3,317,044,064,679,887,385,961,981 (about 3.3 *
+/// 10^24), which covers all of `u64`. Above that, which only `u128` and `i128`
+/// reach, `true` means `n` is a strong probable prime to every base: no such
+/// composite is known, but none has been ruled out either.
+pub fn is_prime<T: Integer>(n: T) -> bool {
+    const BASES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+    let Some(n) = n.to_u128() else {
+        return false;
+    };
+
+    if n < 2 {
+        return false;
+    }
+
+    if let Some(&p) = BASES.iter().find(|&&p| n % p == 0) {
+        return n == p;
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    BASES.iter().all(|&base| {
+        let mut x = mod_pow(base, d, n).expect("n is at least 2");
+
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        (1..s).any(|_| {
+            x = mul_mod(x, x, n).expect("n is at least 2");
+            x == n - 1
+        })
+    })
+}
+
+#[test]
+fn test_gcd() {
+    assert_eq!(gcd(48u32, 18), 6);
+    assert_eq!(gcd(-48i64, 18), 6);
+    assert_eq!(gcd(0u64, 7), 7);
+    assert_eq!(gcd(0u64, 0), 0);
+}
+
+#[test]
+fn test_lcm() {
+    assert_eq!(lcm(48u64, 18), Some(144));
+    assert_eq!(lcm(14_681u64, 20_221), Some(1_071_713));
+    assert_eq!(lcm(234_776_951_771u64, 16_897), Some(14_321_394_058_031));
+    assert_eq!(lcm(-4i64, 6), Some(12));
+    assert_eq!(lcm(u32::MAX, u32::MAX - 1), None);
+}
+
+#[test]
+fn test_extended_gcd() {
+    assert_eq!(extended_gcd(240i64, 46), (2, -9, 47));
+    assert_eq!(extended_gcd(0i64, -5), (5, 0, -1));
+}
+
+#[test]
+fn test_mod_inverse() {
+    assert_eq!(mod_inverse(3u32, 11), Some(4));
+    assert_eq!(mod_inverse(-3i64, 11), Some(7));
+    assert_eq!(mod_inverse(6u64, 9), None);
+    assert_eq!(mod_inverse(5u64, 1), Some(0));
+}
+
+#[test]
+fn test_mul_mod() {
+    assert_eq!(mul_mod(7u32, 8, 5), Some(1));
+    assert_eq!(mul_mod(-7i64, 8, 5), Some(4));
+    assert_eq!(mul_mod(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+    assert_eq!(mul_mod(7u64, 8, 0), None);
+    assert_eq!(mul_mod(0i128, 0, 0), None);
+}
+
+#[test]
+fn test_mod_pow() {
+    assert_eq!(mod_pow(4u32, 13, 497), Some(445));
+    assert_eq!(mod_pow(-2i64, 3, 5), Some(2));
+    assert_eq!(mod_pow(7u64, 0, 1), Some(0));
+    assert_eq!(mod_pow(7u64, 3, 0), None);
+
+    // Fermat's little theorem for the Mersenne prime 2^127 - 1
+    let p = u128::MAX >> 1;
+    assert_eq!(mod_pow(3, p - 1, p), Some(1));
+}
+
+#[test]
+fn test_isqrt() {
+    assert_eq!(isqrt(0u32), Some(0));
+    assert_eq!(isqrt(15u32), Some(3));
+    assert_eq!(isqrt(16u32), Some(4));
+    assert_eq!(isqrt(u64::MAX), Some(u32::MAX as u64));
+    assert_eq!(isqrt(u128::MAX), Some(u64::MAX as u128));
+    assert_eq!(isqrt(-1i64), None);
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt(2u64, 3, 3, 5), Some((8, 15)));
+    assert_eq!(crt(1u64, 4, 5, 6), Some((5, 12)));
+    assert_eq!(crt(1u64, 4, 2, 6), None);
+    assert_eq!(crt(0u64, 1, 4, 7), Some((4, 7)));
+    assert_eq!(crt(-1i64, 4, -1, 6), Some((11, 12)));
+    assert_eq!(crt(0u32, u32::MAX, 0, u32::MAX - 1), None);
+}
+
+#[test]
+fn test_is_prime() {
+    let primes = (0u32..50).filter(|&n| is_prime(n)).collect::<Vec<_>>();
+
+    assert_eq!(
+        primes,
+        [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+    );
+    assert!(is_prime(u128::MAX >> 1));
+    assert!(is_prime(18_446_744_073_709_551_557u64));
+    assert!(!is_prime(3_215_031_751u64));
+    assert!(!is_prime(-7i64));
+
+    // A strong pseudoprime to every prime base up to 37
+    assert!(!is_prime(318_665_857_834_031_151_167_461u128));
+}
+
+#[cfg(test)]
+fn is_prime_brute_force(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_gcd_divides_both(a in 0u64.., b in 0u64..) {
+        let g = gcd(a, b);
+
+        proptest::prop_assume!(g != 0);
+        proptest::prop_assert_eq!(a % g, 0);
+        proptest::prop_assert_eq!(b % g, 0);
+        proptest::prop_assert_eq!(gcd(a / g, b / g), 1);
+        proptest::prop_assert_eq!(gcd(-(a as i128), b as i128), g as i128);
+    }
+
+    #[test]
+    fn test_lcm_matches_wider_type(a in 0u32.., b in 0u32..) {
+        let wide = (a as u64 / gcd(a, b).max(1) as u64) * b as u64;
+
+        proptest::prop_assert_eq!(lcm(a, b), u32::try_from(wide).ok());
+        proptest::prop_assert_eq!(lcm(a as u64, b as u64), Some(wide));
+    }
+
+    #[test]
+    fn test_extended_gcd_is_a_bezout_identity(a in -1_000_000_000i64..1_000_000_000, b in -1_000_000_000i64..1_000_000_000) {
+        let (g, x, y) = extended_gcd(a, b);
+
+        proptest::prop_assert_eq!(g, gcd(a, b));
+        proptest::prop_assert_eq!(a * x + b * y, g);
+    }
+
+    #[test]
+    fn test_mod_inverse_inverts(a in 0u128.., m in 2u128..) {
+        match mod_inverse(a, m) {
+            Some(x) => {
+                proptest::prop_assert!(x < m);
+                proptest::prop_assert_eq!(mul_mod(a, x, m), Some(1));
+            }
+            None => proptest::prop_assert_ne!(gcd(a, m), 1),
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_matches_repeated_multiplication(base in -1_000i64..1_000, exp in 0i64..50, m in 1i64..1_000) {
+        let expected = (0..exp).fold(1 % m, |acc, _| (acc * base).rem_euclid(m));
+
+        proptest::prop_assert_eq!(mod_pow(base, exp, m), Some(expected));
+        proptest::prop_assert_eq!(mod_pow(base as i128, exp as i128, m as i128), Some(expected as i128));
+    }
+
+    #[test]
+    fn test_mul_mod_matches_wider_type(a in 0u64.., b in 0u64.., m in 1u64..) {
+        let expected = (a as u128 * b as u128 % m as u128) as u64;
+
+        proptest::prop_assert_eq!(mul_mod(a, b, m), Some(expected));
+        proptest::prop_assert_eq!(mul_mod(a as u128, b as u128, m as u128), Some(expected as u128));
+    }
+
+    #[test]
+    fn test_isqrt_brackets_the_root(n in 0u64..) {
+        let r = isqrt(n).unwrap() as u128;
+
+        proptest::prop_assert!(r * r <= n as u128);
+        proptest::prop_assert!((r + 1) * (r + 1) > n as u128);
+    }
+
+    #[test]
+    fn test_isqrt_matches_std(n in 0u128..) {
+        proptest::prop_assert_eq!(isqrt(n), Some(n.isqrt()));
+    }
+
+    #[test]
+    fn test_crt_matches_brute_force(a in 0u64..100, m in 1u64..100, b in 0u64..100, n in 1u64..100) {
+        let solution = (0..m * n).find(|x| x % m == a % m && x % n == b % n);
+
+        match crt(a, m, b, n) {
+            Some((x, modulus)) => {
+                proptest::prop_assert_eq!(modulus, lcm(m, n).unwrap());
+                proptest::prop_assert_eq!(Some(x), solution);
+            }
+            None => proptest::prop_assert_eq!(solution, None),
+        }
+    }
+
+    #[test]
+    fn test_is_prime_matches_trial_division(n in 0u64..1_000_000) {
+        proptest::prop_assert_eq!(is_prime(n), is_prime_brute_force(n));
+        proptest::prop_assert_eq!(is_prime(n as u32), is_prime(n as i64));
+    }
+}
@@ -0,0 +1,209 @@
+//! Counts what the solvers allocate. With the `alloc-stats` feature, and always
+//! in this crate's unit tests, a counting allocator wraps the system one. It
+//! keeps separate totals for each thread, so tests running side by side don't
+//! disturb each other's numbers.
+
+use std::fmt;
+
+/// What a piece of code allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// How many times it asked for memory, growing or shrinking an allocation included.
+    pub allocations: u64,
+    /// How many bytes those requests added up to.
+    pub bytes: u64,
+    /// The most heap it held at once, not counting what was in use before it started.
+    pub peak: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// A number of bytes, in the largest unit it has at least one of.
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+        let Bytes(bytes) = *self;
+
+        if bytes < 1024 {
+            return write!(f, "{} B", bytes);
+        }
+
+        let mut value = bytes as f64 / 1024.0;
+        let mut unit = 0;
+
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(any(feature = "alloc-stats", test))]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    /// One thread's running totals.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Totals {
+        pub allocations: u64,
+        pub bytes: u64,
+        /// Can dip below 0 when this thread frees memory another thread allocated.
+        pub current: i64,
+        pub peak: i64,
+    }
+
+    thread_local! {
+        // A `const` initializer and no destructor, so the allocator can use it at any time
+        pub static TOTALS: Cell<Totals> = const {
+            Cell::new(Totals {
+                allocations: 0,
+                bytes: 0,
+                current: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    fn record(allocated: usize, freed: usize) {
+        let _ = TOTALS.try_with(|totals| {
+            let mut t = totals.get();
+
+            if allocated > 0 {
+                t.allocations += 1;
+                t.bytes += allocated as u64;
+            }
+
+            t.current += allocated as i64 - freed as i64;
+            t.peak = t.peak.max(t.current);
+            totals.set(t);
+        });
+    }
+
+    pub struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(0, layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+
+            if !new_ptr.is_null() {
+                record(new_size, layout.size());
+            }
+
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+/// Runs `f`, counting what it allocates on this thread. Without the counting
+/// allocator there is nothing counted, and this only runs `f`.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(any(feature = "alloc-stats", test))]
+    {
+        use counting::TOTALS;
+
+        // Only the peak reached while `f` runs is interesting
+        let before = TOTALS.with(|totals| {
+            let mut t = totals.get();
+            t.peak = t.current;
+            totals.set(t);
+            t
+        });
+
+        let result = f();
+        let after = TOTALS.with(|totals| totals.get());
+
+        let stats = AllocStats {
+            allocations: after.allocations - before.allocations,
+            bytes: after.bytes - before.bytes,
+            peak: (after.peak - before.current) as u64,
+        };
+
+        (result, Some(stats))
+    }
+
+    #[cfg(not(any(feature = "alloc-stats", test)))]
+    (f(), None)
+}
+
+#[test]
+fn test_measure() {
+    let ((), stats) = measure(|| {
+        let first = vec![0u8; 100];
+        drop(first);
+
+        let mut second = Vec::<u8>::with_capacity(40);
+        second.reserve_exact(60);
+    });
+
+    // The second vector grows in place or moves, either way one request
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            allocations: 3,
+            bytes: 200,
+            peak: 100,
+        })
+    );
+}
+
+#[test]
+fn test_display() {
+    let stats = AllocStats {
+        allocations: 3,
+        bytes: 1536,
+        peak: 3 << 20,
+    };
+
+    assert_eq!(
+        stats.to_string(),
+        "3 allocations, 1.5 KiB allocated, 3.0 MiB peak"
+    );
+    assert_eq!(Bytes(1023).to_string(), "1023 B");
+    assert_eq!(Bytes(5 << 40).to_string(), "5120.0 GiB");
+}
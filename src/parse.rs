@@ -0,0 +1,276 @@
+//! Zero-copy parsing. Tokens and numbers are read straight from the input's
+//! bytes, so a solver can work through its input without allocating. Only
+//! errors allocate.
+
+use std::str::{self, FromStr};
+
+use bstr::ByteSlice;
+
+use crate::{Error, Result};
+
+/// Reads through part of an input, pointing errors at where they are in the whole input.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    input: &'a [u8],
+    rest: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Cursor { input, rest: input }
+    }
+
+    /// A cursor over `span`, which must be a slice of `input`.
+    pub fn within(input: &'a [u8], span: &'a [u8]) -> Self {
+        Cursor { input, rest: span }
+    }
+
+    /// A cursor over `span`, which must be a slice of the same input.
+    pub fn at(&self, span: &'a [u8]) -> Self {
+        Cursor::within(self.input, span)
+    }
+
+    /// Everything not read yet.
+    pub fn rest(&self) -> &'a [u8] {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// An error pointing at `span`, which must be a slice of the input.
+    pub fn error_at(&self, span: &[u8], message: impl Into<String>) -> Error {
+        Error::at_bytes(self.input, span, message)
+    }
+
+    /// An error pointing at the first byte not read yet.
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        self.error_at(self.rest, message)
+    }
+
+    /// Skips whitespace, then stops reading at the last byte that isn't whitespace.
+    pub fn trim(&mut self) -> &mut Self {
+        self.rest = self.rest.trim();
+        self
+    }
+
+    /// Reads `token` if what's left starts with it.
+    pub fn eat(&mut self, token: &str) -> bool {
+        match self.rest.strip_prefix(token.as_bytes()) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Like [`Cursor::eat`], but errors unless what's left starts with `token`.
+    pub fn expect(&mut self, token: &str) -> Result<()> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(format!("expected {:?}", token))),
+        }
+    }
+
+    /// Everything up to the next `delimiter`, which is read too. Reads nothing
+    /// when there is no `delimiter` left.
+    pub fn until(&mut self, delimiter: u8) -> Option<&'a [u8]> {
+        let idx = self.rest.find_byte(delimiter)?;
+        let before = &self.rest[..idx];
+
+        self.rest = &self.rest[idx + 1..];
+        Some(before)
+    }
+
+    /// A cursor over each piece of what's left between `delimiter`s, split like [`str::split`].
+    pub fn split(self, delimiter: u8) -> impl Iterator<Item = Cursor<'a>> {
+        self.rest
+            .split(move |&b| b == delimiter)
+            .map(move |piece| self.at(piece))
+    }
+
+    /// Skips whitespace and reads the bytes up to the next whitespace. Empty at the end.
+    pub fn token(&mut self) -> &'a [u8] {
+        let start = self.rest.len() - self.rest.trim_start().len();
+        let rest = &self.rest[start..];
+        let end = rest
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(rest.len());
+
+        self.rest = &rest[end..];
+        &rest[..end]
+    }
+
+    /// Reads the next token as a number.
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        let token = self.token();
+
+        self.parse_number(token)
+    }
+
+    /// Parses `token`, a slice of the input, as a number.
+    pub fn parse_number<T: FromStr>(&self, token: &[u8]) -> Result<T> {
+        number(self.input, token)
+    }
+
+    /// The numbers separated by whitespace in what's left, reading them all.
+    pub fn numbers<T: FromStr>(mut self) -> impl Iterator<Item = Result<T>> + 'a {
+        std::iter::from_fn(move || match self.token() {
+            b"" => None,
+            token => Some(number(self.input, token)),
+        })
+    }
+}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &[u8], token: &[u8]) -> Result<T> {
+    str::from_utf8(token)
+        .ok()
+        .and_then(|token| token.parse().ok())
+        .ok_or_else(|| {
+            Error::at_bytes(
+                input,
+                token,
+                format!("expected a number, found {:?}", token.as_bstr()),
+            )
+        })
+}
+
+/// A cursor over each line of `input`, split like [`str::lines`].
+pub fn lines(input: &[u8]) -> impl Iterator<Item = Cursor<'_>> {
+    input.lines().map(move |line| Cursor::within(input, line))
+}
+
+/// Rows of values of different lengths, stored one after another in a single
+/// buffer rather than in a `Vec` each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rows<T> {
+    values: Vec<T>,
+    ends: Vec<usize>,
+}
+
+impl<T> Default for Rows<T> {
+    fn default() -> Self {
+        Rows {
+            values: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+}
+
+impl<T> Rows<T> {
+    /// Adds a value to the row being built.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    /// Finishes the row being built, which may be empty.
+    pub fn end_row(&mut self) {
+        self.ends.push(self.values.len());
+    }
+
+    /// Adds a whole row at once.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        self.values.extend(row);
+        self.end_row();
+    }
+
+    /// The values pushed since the last row ended.
+    pub fn current_mut(&mut self) -> &mut [T] {
+        let start = self.ends.last().copied().unwrap_or(0);
+
+        &mut self.values[start..]
+    }
+
+    /// How many rows have ended.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let starts = std::iter::once(0).chain(self.ends.iter().copied());
+
+        starts
+            .zip(&self.ends)
+            .map(|(start, &end)| &self.values[start..end])
+    }
+}
+
+#[test]
+fn test_cursor() {
+    let input = b"Card 1: 41 48 | 83  86\nCard x: 1 | 2";
+    let mut lines = lines(input);
+    let mut line = lines.next().unwrap();
+
+    line.expect("Card").unwrap();
+    assert_eq!(
+        line.number::<u64>(),
+        Err(Error::parse(1, 6, "expected a number, found \"1:\""))
+    );
+
+    let mut line = lines.next().unwrap();
+
+    assert!(!line.eat("Cards"));
+    assert_eq!(
+        line.expect("Card:"),
+        Err(Error::parse(2, 1, "expected \"Card:\""))
+    );
+    assert_eq!(line.until(b':'), Some(&b"Card x"[..]));
+    assert_eq!(line.until(b':'), None);
+
+    let mut pieces = line.split(b'|');
+    assert_eq!(pieces.next().unwrap().trim().rest(), b"1");
+    assert_eq!(
+        pieces
+            .next()
+            .unwrap()
+            .numbers()
+            .collect::<Result<Vec<u8>>>(),
+        Ok(vec![2])
+    );
+    assert!(pieces.next().is_none());
+    assert_eq!(Cursor::new(b"").split(b'|').count(), 1);
+
+    let mut first = Cursor::new(input);
+    first.until(b'|');
+    assert_eq!(
+        first.numbers::<u8>().collect::<Vec<_>>(),
+        vec![
+            Ok(83),
+            Ok(86),
+            Err(Error::parse(2, 1, "expected a number, found \"Card\"")),
+            Err(Error::parse(2, 6, "expected a number, found \"x:\"")),
+            Ok(1),
+            Err(Error::parse(2, 11, "expected a number, found \"|\"")),
+            Ok(2),
+        ]
+    );
+}
+
+#[test]
+fn test_rows() {
+    let mut rows = Rows::default();
+
+    rows.push_row([1, 2, 3]);
+    rows.end_row();
+    rows.push(4);
+    rows.current_mut()[0] += 1;
+    rows.push(6);
+
+    // The row being built isn't one of the rows yet
+    assert_eq!(rows.len(), 2);
+    rows.end_row();
+
+    assert_eq!(
+        rows.iter().collect::<Vec<_>>(),
+        [&[1, 2, 3][..], &[], &[5, 6]]
+    );
+    assert!(Rows::<u8>::default().is_empty());
+}
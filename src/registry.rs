@@ -0,0 +1,197 @@
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+use crate::{parse::Rows, Result};
+
+/// Lets the registry treat fallible and infallible generators and solvers alike.
+pub trait IntoResult {
+    type Output;
+
+    fn into_result(self) -> Result<Self::Output>;
+}
+
+impl<T> IntoResult for Result<T> {
+    type Output = T;
+
+    fn into_result(self) -> Result<T> {
+        self
+    }
+}
+
+impl<T> IntoResult for Vec<T> {
+    type Output = Vec<T>;
+
+    fn into_result(self) -> Result<Vec<T>> {
+        Ok(self)
+    }
+}
+
+impl<T> IntoResult for Rows<T> {
+    type Output = Rows<T>;
+
+    fn into_result(self) -> Result<Rows<T>> {
+        Ok(self)
+    }
+}
+
+macro_rules! impl_into_result {
+    ($($t:ty),*) => {
+        $(
+            impl IntoResult for $t {
+                type Output = $t;
+
+                fn into_result(self) -> Result<$t> {
+                    Ok(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_result!(u64, usize, i64, u128, i128);
+
+/// The two steps of every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The generator, turning the input into what the solver takes.
+    Parse,
+    /// The solver, from the parsed input to the answer.
+    Solve,
+}
+
+/// Wraps each step of a run, to time it or count what it allocates.
+pub trait Steps {
+    /// Runs one step. `run` must be called exactly once.
+    fn run(&mut self, step: Step, run: &mut dyn FnMut());
+}
+
+/// Runs every step as it is.
+impl Steps for () {
+    fn run(&mut self, _step: Step, run: &mut dyn FnMut()) {
+        run()
+    }
+}
+
+/// One part of one day's puzzle, from raw input to the answer.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Parses the input and solves the puzzle from it. Parsing and solving
+    /// happen in one call, so what the generator returns can borrow the input.
+    pub runner: fn(&str, &mut dyn Steps) -> Result<String>,
+}
+
+impl Solution {
+    /// Where this day's puzzle input lives, relative to the crate root.
+    pub fn input_path(&self) -> PathBuf {
+        self.input_path_in(Path::new("input"))
+    }
+
+    /// Where this day's puzzle input lives in a directory laid out like `input`.
+    pub fn input_path_in(&self, dir: &Path) -> PathBuf {
+        dir.join(self.year.to_string())
+            .join(format!("day{}.txt", self.day))
+    }
+
+    /// Parses the input and solves the puzzle, handing each step to `steps`.
+    /// Like cargo-aoc, this strips the input's trailing newline first.
+    pub fn run(&self, input: &str, steps: &mut dyn Steps) -> Result<String> {
+        (self.runner)(input.trim_end_matches(['\r', '\n']), steps)
+    }
+
+    /// Parses the input and solves the puzzle.
+    pub fn solve(&self, input: &str) -> Result<String> {
+        self.run(input, &mut ())
+    }
+}
+
+/// Shows the puzzle's address, like `2023/5/2`.
+impl Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.year, self.day, self.part)
+    }
+}
+
+/// Formats an answer the same way no matter which type the solver returns.
+fn answer<T: Display>(answer: impl IntoResult<Output = T>) -> Result<String> {
+    answer.into_result().map(|answer| answer.to_string())
+}
+
+/// Runs `f` as one step of a run, handing back what it returned.
+fn step<T>(steps: &mut dyn Steps, step: Step, f: impl FnOnce() -> T) -> T {
+    let mut f = Some(f);
+    let mut output = None;
+
+    steps.run(step, &mut || output = f.take().map(|f| f()));
+    output.expect("a step was never run")
+}
+
+macro_rules! solution {
+    ($year:literal, $day:literal, $part:literal, $generator:path, $solver:path) => {
+        Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            runner: |input, steps| {
+                let parsed = step(steps, Step::Parse, || $generator(input).into_result())?;
+                step(steps, Step::Solve, || answer($solver(&parsed)))
+            },
+        }
+    };
+}
+
+/// Every solver, ordered by year, day and part.
+// One entry per line, so that `new-day` can find its place among them
+#[rustfmt::skip]
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(2023, 1, 1, crate::y2023::day1::generator, crate::y2023::day1::part1),
+    solution!(2023, 1, 2, crate::y2023::day1::generator, crate::y2023::day1::part2),
+    solution!(2023, 2, 1, crate::y2023::day2::generator, crate::y2023::day2::part1),
+    solution!(2023, 2, 2, crate::y2023::day2::generator, crate::y2023::day2::part2),
+    solution!(2023, 3, 1, crate::y2023::day3::generator, crate::y2023::day3::part1),
+    solution!(2023, 3, 2, crate::y2023::day3::generator, crate::y2023::day3::part2),
+    solution!(2023, 4, 1, crate::y2023::day4::generator, crate::y2023::day4::part1),
+    solution!(2023, 4, 2, crate::y2023::day4::generator, crate::y2023::day4::part2),
+    solution!(2023, 5, 1, crate::y2023::day5::generator, crate::y2023::day5::part1),
+    solution!(2023, 5, 2, crate::y2023::day5::generator, crate::y2023::day5::part2),
+    solution!(2023, 6, 1, crate::y2023::day6::generator_part1, crate::y2023::day6::part1),
+    solution!(2023, 6, 2, crate::y2023::day6::generator_part2, crate::y2023::day6::part2),
+    solution!(2023, 7, 1, crate::y2023::day7::generator, crate::y2023::day7::part1),
+    solution!(2023, 7, 2, crate::y2023::day7::generator, crate::y2023::day7::part2),
+    solution!(2023, 8, 1, crate::y2023::day8::generator, crate::y2023::day8::part1),
+    solution!(2023, 8, 2, crate::y2023::day8::generator, crate::y2023::day8::part2),
+    solution!(2023, 9, 1, crate::y2023::day9::generator, crate::y2023::day9::part1),
+    solution!(2023, 9, 2, crate::y2023::day9::generator, crate::y2023::day9::part2),
+];
+
+/// The most recent year with any solvers.
+pub fn latest_year() -> u16 {
+    SOLUTIONS.iter().map(|s| s.year).max().unwrap_or(2023)
+}
+
+pub fn find(year: u16, day: u8, part: u8) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.year == year && s.day == day && s.part == part)
+}
+
+#[test]
+fn test_solutions() {
+    assert!(SOLUTIONS
+        .windows(2)
+        .all(|pair| (pair[0].year, pair[0].day, pair[0].part)
+            < (pair[1].year, pair[1].day, pair[1].part)));
+
+    assert_eq!(
+        find(2023, 6, 2)
+            .unwrap()
+            .solve("Time:      7  15   30\nDistance:  9  40  200"),
+        Ok("71503".to_string())
+    );
+
+    assert!(find(2023, 6, 3).is_none());
+}
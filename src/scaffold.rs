@@ -0,0 +1,365 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Result};
+
+/// The year named in `aoc_lib!`. cargo-aoc only knows about one year, so only
+/// its days get the `aoc` attributes.
+pub const AOC_RUNNER_YEAR: u16 = 2023;
+
+const TEMPLATE: &str = r#"use crate::{Error, Result};
+
+#[cfg_attr(feature = "runner", aoc_generator(day{day}))]
+pub fn generator(input: &str) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_string).collect())
+}
+
+#[cfg_attr(feature = "runner", aoc(day{day}, part1))]
+pub fn part1(_input: &[String]) -> Result<u64> {
+    Err(Error::NoSolution("part 1 isn't solved yet".to_string()))
+}
+
+#[cfg_attr(feature = "runner", aoc(day{day}, part2))]
+pub fn part2(_input: &[String]) -> Result<u64> {
+    Err(Error::NoSolution("part 2 isn't solved yet".to_string()))
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = include_str!("../../input/{year}/day{day}.example.txt");
+
+#[test]
+#[ignore = "the example's answer hasn't been filled in yet"]
+fn test_part1() {
+    assert_eq!(part1(&generator(EXAMPLE).unwrap()), Ok(0));
+}
+
+#[test]
+#[ignore = "the example's answer hasn't been filled in yet"]
+fn test_part2() {
+    assert_eq!(part2(&generator(EXAMPLE).unwrap()), Ok(0));
+}
+"#;
+
+/// Inserts `line` into a sorted run of lines. `matches` returns `None` for lines
+/// outside the run, and whether the line belongs after the new one otherwise.
+fn insert_line(source: &str, line: &str, matches: impl Fn(&str) -> Option<bool>) -> Option<String> {
+    let lines = source.lines().collect::<Vec<_>>();
+
+    let index = match lines.iter().position(|l| matches(l) == Some(true)) {
+        Some(index) => index,
+        None => lines.iter().rposition(|l| matches(l).is_some())? + 1,
+    };
+
+    let mut lines = lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    lines.insert(index, line.to_string());
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// The day number of a `pub mod dayN;` line.
+fn module_day(line: &str) -> Option<u8> {
+    line.strip_prefix("pub mod day")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// The year of a `pub mod yYYYY;` line.
+fn module_year(line: &str) -> Option<u16> {
+    line.strip_prefix("pub mod y")?
+        .strip_suffix(';')?
+        .parse()
+        .ok()
+}
+
+/// The year, day and part of a `solution!(...)` line in the registry.
+fn solution_key(line: &str) -> Option<(u16, u8, u8)> {
+    let mut args = line
+        .trim()
+        .strip_prefix("solution!(")?
+        .split(',')
+        .map(str::trim);
+
+    Some((
+        args.next()?.parse().ok()?,
+        args.next()?.parse().ok()?,
+        args.next()?.parse().ok()?,
+    ))
+}
+
+/// Creates everything a new day needs under the crate at `root`: the module
+/// with a generator, both parts and example tests, its place in the year's
+/// module and the solver registry, and empty input and example files. The
+/// first day of a new year also creates the year's module and adds it to `lib.rs`.
+///
+/// Nothing is written if any of it already exists, and nothing is left behind
+/// if a write fails. Returns the files touched.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Invalid(format!(
+            "{} is not a day between 1 and 25",
+            day
+        )));
+    }
+
+    let module = root.join(format!("src/y{}/day{}.rs", year, day));
+    let input = root.join(format!("input/{}/day{}.txt", year, day));
+    let example = root.join(format!("input/{}/day{}.example.txt", year, day));
+    let year_module = root.join(format!("src/y{}/mod.rs", year));
+    let lib = root.join("src/lib.rs");
+    let registry = root.join("src/registry.rs");
+
+    if let Some(existing) = [&module, &input, &example].into_iter().find(|p| p.exists()) {
+        return Err(Error::Invalid(format!(
+            "{} already exists, refusing to overwrite it",
+            existing.display()
+        )));
+    }
+
+    let read = |path: &Path| fs::read_to_string(path).map_err(|e| Error::io(path, e));
+    let registry_source = read(&registry)?;
+
+    // A new year starts with an empty module, which `lib.rs` has to declare
+    let (year_source, lib_source) = if year_module.exists() {
+        (read(&year_module)?, None)
+    } else {
+        let lib_source = insert_line(&read(&lib)?, &format!("pub mod y{};", year), |l| {
+            module_year(l).map(|y| y > year)
+        })
+        .ok_or_else(|| Error::Invalid(format!("no year modules found in {}", lib.display())))?;
+
+        (String::new(), Some(lib_source))
+    };
+
+    if year_source.lines().any(|l| module_day(l) == Some(day)) {
+        return Err(Error::Invalid(format!(
+            "day {} is already declared in {}",
+            day,
+            year_module.display()
+        )));
+    }
+
+    if registry_source
+        .lines()
+        .any(|l| solution_key(l).is_some_and(|(y, d, _)| (y, d) == (year, day)))
+    {
+        return Err(Error::Invalid(format!(
+            "day {} of {} is already registered in {}",
+            day,
+            year,
+            registry.display()
+        )));
+    }
+
+    let module_line = format!("pub mod day{};", day);
+    let year_source = if year_source.is_empty() {
+        module_line + "\n"
+    } else {
+        // rustfmt sorts module declarations by name, so day10 comes before day2
+        insert_line(&year_source, &module_line, |l| {
+            module_day(l).map(|d| d.to_string() > day.to_string())
+        })
+        .ok_or_else(|| {
+            Error::Invalid(format!("no day modules found in {}", year_module.display()))
+        })?
+    };
+
+    let mut registry_source = registry_source;
+
+    for part in 1..=2 {
+        let line = format!(
+            "    solution!({year}, {day}, {part}, crate::y{year}::day{day}::generator, crate::y{year}::day{day}::part{part}),",
+            year = year,
+            day = day,
+            part = part
+        );
+
+        registry_source = insert_line(&registry_source, &line, |l| {
+            solution_key(l).map(|key| key > (year, day, part))
+        })
+        .ok_or_else(|| Error::Invalid(format!("no solutions found in {}", registry.display())))?;
+    }
+
+    let source = TEMPLATE
+        .lines()
+        .filter(|l| year == AOC_RUNNER_YEAR || !l.starts_with("#[cfg_attr(feature = \"runner\""))
+        .map(|l| {
+            l.replace("{day}", &day.to_string())
+                .replace("{year}", &year.to_string())
+                + "\n"
+        })
+        .collect::<String>();
+
+    let mut files = vec![(year_module, year_source), (registry, registry_source)];
+
+    if let Some(lib_source) = lib_source {
+        files.push((lib, lib_source));
+    }
+
+    files.extend([
+        (module, source),
+        (input, String::new()),
+        (example, String::new()),
+    ]);
+
+    write_all(&files)?;
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Writes every file, creating directories as needed. When a write fails, the
+/// ones before it are undone, so no day is left half created.
+fn write_all(files: &[(PathBuf, String)]) -> Result<()> {
+    // What each file held before, and the directories made for them
+    let mut previous = Vec::new();
+    let mut created = Vec::new();
+
+    let result = files.iter().try_for_each(|(path, contents)| {
+        if let Some(dir) = path.ancestors().skip(1).take_while(|p| !p.exists()).last() {
+            created.push(dir);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+        }
+
+        previous.push((path, fs::read_to_string(path).ok()));
+        fs::write(path, contents).map_err(|e| Error::io(path, e))
+    });
+
+    if result.is_err() {
+        for (path, contents) in previous.into_iter().rev() {
+            let _ = match contents {
+                Some(contents) => fs::write(path, contents),
+                None => fs::remove_file(path),
+            };
+        }
+
+        for dir in created.into_iter().rev() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_insert_line() {
+    let lib = "pub mod grid;\n\npub mod day1;\npub mod day3;\n\npub use error::Error;\n";
+
+    assert_eq!(
+        insert_line(lib, "pub mod day2;", |l| module_day(l).map(|d| d > 2)),
+        Some("pub mod grid;\n\npub mod day1;\npub mod day2;\npub mod day3;\n\npub use error::Error;\n".to_string())
+    );
+
+    assert_eq!(
+        insert_line(lib, "pub mod day4;", |l| module_day(l).map(|d| d > 4)),
+        Some("pub mod grid;\n\npub mod day1;\npub mod day3;\npub mod day4;\n\npub use error::Error;\n".to_string())
+    );
+
+    assert_eq!(module_year("pub mod y2023;"), Some(2023));
+    assert_eq!(module_year("pub mod grid;"), None);
+
+    assert_eq!(
+        solution_key(
+            "    solution!(2023, 9, 2, crate::y2023::day9::generator, crate::y2023::day9::part2),"
+        ),
+        Some((2023, 9, 2))
+    );
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("src/y2023")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub mod grid;\n\npub mod y2023;\n").unwrap();
+    fs::write(
+        root.join("src/y2023/mod.rs"),
+        "pub mod day1;\npub mod day9;\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src/registry.rs"),
+        "pub const SOLUTIONS: &[Solution] = &[\n    solution!(2023, 1, 1, crate::y2023::day1::generator, crate::y2023::day1::part1),\n    solution!(2023, 9, 2, crate::y2023::day9::generator, crate::y2023::day9::part2),\n];\n",
+    )
+    .unwrap();
+
+    // A failed write undoes the ones before it, here with `input` in the way of its directory
+    fs::write(root.join("input"), "").unwrap();
+
+    let sources = ["src/lib.rs", "src/y2023/mod.rs", "src/registry.rs"]
+        .map(|path| fs::read_to_string(root.join(path)).unwrap());
+
+    assert!(new_day(&root, 2023, 3).is_err());
+    assert!(!root.join("src/y2023/day3.rs").exists());
+
+    assert!(new_day(&root, 2024, 1).is_err());
+    assert!(!root.join("src/y2024").exists());
+    assert_eq!(
+        ["src/lib.rs", "src/y2023/mod.rs", "src/registry.rs"]
+            .map(|path| fs::read_to_string(root.join(path)).unwrap()),
+        sources
+    );
+
+    fs::remove_file(root.join("input")).unwrap();
+
+    assert_eq!(new_day(&root, 2023, 3).map(|files| files.len()), Ok(5));
+
+    let module = fs::read_to_string(root.join("src/y2023/day3.rs")).unwrap();
+    assert!(module.contains("#[cfg_attr(feature = \"runner\", aoc(day3, part2))]"));
+    assert!(module.contains("include_str!(\"../../input/2023/day3.example.txt\")"));
+
+    assert_eq!(
+        fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap(),
+        "pub mod day1;\npub mod day3;\npub mod day9;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("src/registry.rs")).unwrap(),
+        "pub const SOLUTIONS: &[Solution] = &[\n    solution!(2023, 1, 1, crate::y2023::day1::generator, crate::y2023::day1::part1),\n    solution!(2023, 3, 1, crate::y2023::day3::generator, crate::y2023::day3::part1),\n    solution!(2023, 3, 2, crate::y2023::day3::generator, crate::y2023::day3::part2),\n    solution!(2023, 9, 2, crate::y2023::day9::generator, crate::y2023::day9::part2),\n];\n"
+    );
+    assert!(root.join("input/2023/day3.txt").exists());
+
+    // Running it again must leave the first attempt alone
+    fs::write(root.join("src/y2023/day3.rs"), "// solved").unwrap();
+
+    assert!(new_day(&root, 2023, 3).is_err());
+    assert_eq!(
+        fs::read_to_string(root.join("src/y2023/day3.rs")).unwrap(),
+        "// solved"
+    );
+
+    fs::remove_file(root.join("src/y2023/day3.rs")).unwrap();
+    fs::remove_file(root.join("input/2023/day3.txt")).unwrap();
+    fs::remove_file(root.join("input/2023/day3.example.txt")).unwrap();
+
+    assert_eq!(
+        new_day(&root, 2023, 3),
+        Err(Error::Invalid(format!(
+            "day 3 is already declared in {}",
+            root.join("src/y2023/mod.rs").display()
+        )))
+    );
+
+    // The first day of a year creates the year's module, without cargo-aoc attributes
+    assert_eq!(new_day(&root, 2024, 1).map(|files| files.len()), Ok(6));
+
+    let module = fs::read_to_string(root.join("src/y2024/day1.rs")).unwrap();
+    assert!(!module.contains("aoc("));
+    assert!(module.contains("pub fn part2("));
+
+    assert_eq!(
+        fs::read_to_string(root.join("src/y2024/mod.rs")).unwrap(),
+        "pub mod day1;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "pub mod grid;\n\npub mod y2023;\npub mod y2024;\n"
+    );
+    assert!(fs::read_to_string(root.join("src/registry.rs"))
+        .unwrap()
+        .ends_with("    solution!(2024, 1, 2, crate::y2024::day1::generator, crate::y2024::day1::part2),\n];\n"));
+
+    fs::remove_dir_all(root).unwrap();
+}
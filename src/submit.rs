@@ -0,0 +1,444 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Value};
+
+use crate::{fetch::Client, Error, Result};
+
+/// Where every submission is recorded, relative to the crate root.
+pub const ATTEMPTS_PATH: &str = "attempts.json";
+
+/// How the puzzle server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way.
+    Wrong,
+    /// Not judged, because the last answer was sent too recently.
+    Wait(Duration),
+    /// Not judged, because this part was already solved.
+    AlreadySolved,
+}
+
+/// Parses a wait like "1m 5s" or "35s".
+fn parse_wait(s: &str) -> Option<Duration> {
+    s.split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let unit = match part.chars().last()? {
+                'h' => 3_600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+
+            let n = part[..part.len() - 1].parse::<u64>().ok()?;
+
+            Some(total + Duration::from_secs(n * unit))
+        })
+}
+
+impl Outcome {
+    /// Reads the outcome from the page the server answers a submission with.
+    pub fn parse(page: &str) -> Option<Self> {
+        let page = page.to_lowercase();
+
+        if page.contains("that's the right answer") {
+            Some(Outcome::Correct)
+        } else if page.contains("you gave an answer too recently") {
+            let end = page.find(" left to wait")?;
+            let start = page[..end].rfind("you have ")? + "you have ".len();
+
+            parse_wait(&page[start..end]).map(Outcome::Wait)
+        } else if page.contains("that's not the right answer") {
+            Some(if page.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            })
+        } else if page.contains("you don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::Wait(_) => "wait",
+            Outcome::AlreadySolved => "already solved",
+        }
+    }
+
+    /// Whether the server actually judged the answer, and found it wrong.
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Wait(wait) => write!(f, "wait {}s before trying again", wait.as_secs()),
+            outcome => write!(f, "{}", outcome.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Attempt {
+    fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "outcome": self.outcome.name(),
+            "wait": match self.outcome {
+                Outcome::Wait(wait) => Some(wait.as_secs()),
+                _ => None,
+            },
+            "submitted_at": self.submitted_at,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let number = |key: &str| value.get(key)?.as_u64();
+
+        let outcome = match value.get("outcome")?.as_str()? {
+            "correct" => Outcome::Correct,
+            "too high" => Outcome::TooHigh,
+            "too low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "wait" => Outcome::Wait(Duration::from_secs(number("wait")?)),
+            "already solved" => Outcome::AlreadySolved,
+            _ => return None,
+        };
+
+        Some(Attempt {
+            year: number("year")?.try_into().ok()?,
+            day: number("day")?.try_into().ok()?,
+            part: number("part")?.try_into().ok()?,
+            answer: value.get("answer")?.as_str()?.to_string(),
+            outcome,
+            submitted_at: number("submitted_at")?,
+        })
+    }
+}
+
+/// Every answer submitted so far, kept in a JSON file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempts {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Loads the attempts saved at `path`. A missing file means no attempts yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        let attempts = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str::<Vec<Value>>(&contents)
+                .ok()
+                .and_then(|values| values.iter().map(Attempt::from_json).collect())
+                .ok_or_else(|| {
+                    Error::Invalid(format!("{} is not a list of attempts", path.display()))
+                })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(Error::io(&path, e)),
+        };
+
+        Ok(Attempts { path, attempts })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn for_puzzle(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| (a.year, a.day, a.part) == (year, day, part))
+    }
+
+    /// Why `answer` shouldn't be sent at `now`, judging by the earlier attempts.
+    pub fn refusal(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let attempts = self.for_puzzle(year, day, part).collect::<Vec<_>>();
+
+        if let Some(solved) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Some(format!(
+                "part {} of day {} of {} was already solved with {}",
+                part, day, year, solved.answer
+            ));
+        }
+
+        if let Some(Attempt {
+            outcome: Outcome::Wait(wait),
+            submitted_at,
+            ..
+        }) = attempts.last()
+        {
+            let ready = submitted_at + wait.as_secs();
+
+            if now < ready {
+                return Some(format!("wait {}s before trying again", ready - now));
+            }
+        }
+
+        if let Some(rejected) = attempts
+            .iter()
+            .find(|a| a.outcome.is_rejection() && a.answer == answer)
+        {
+            return Some(format!(
+                "{} was already rejected as {}",
+                answer, rejected.outcome
+            ));
+        }
+
+        let number = answer.parse::<i128>().ok()?;
+        let bound = |outcome| {
+            attempts
+                .iter()
+                .filter(move |a| a.outcome == outcome)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| number >= high) {
+            return Some(format!(
+                "{} can't be right, {} was already too high",
+                number, high
+            ));
+        }
+
+        if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| number <= low) {
+            return Some(format!(
+                "{} can't be right, {} was already too low",
+                number, low
+            ));
+        }
+
+        None
+    }
+
+    fn record(&mut self, attempt: Attempt) -> Result<()> {
+        self.attempts.push(attempt);
+
+        let values = self
+            .attempts
+            .iter()
+            .map(Attempt::to_json)
+            .collect::<Vec<_>>();
+        let contents = serde_json::to_string_pretty(&values).expect("attempts always serialize");
+
+        fs::write(&self.path, contents + "\n").map_err(|e| Error::io(&self.path, e))
+    }
+}
+
+/// Escapes everything but letters, digits and `-_.~` for a form body.
+fn form_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Sends answers to the puzzle server, refusing any the earlier attempts
+/// already show to be wrong.
+pub struct Submitter {
+    client: Client,
+    attempts: Attempts,
+}
+
+impl Submitter {
+    pub fn new(client: Client, attempts: Attempts) -> Self {
+        Submitter { client, attempts }
+    }
+
+    pub fn attempts(&self) -> &Attempts {
+        &self.attempts
+    }
+
+    pub fn submit(&mut self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome> {
+        let answer = answer.trim();
+
+        if answer.is_empty() {
+            return Err(Error::Invalid("the answer is empty".to_string()));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if let Some(reason) = self.attempts.refusal(year, day, part, answer, now) {
+            return Err(Error::Invalid(format!("not submitting: {}", reason)));
+        }
+
+        let (status, page) = self.client.send(
+            &format!("/{}/day/{}/answer", year, day),
+            Some(&format!("level={}&answer={}", part, form_encode(answer))),
+        )?;
+
+        if status != 200 {
+            return Err(Error::Remote(match status {
+                300..=399 | 400 | 401 | 403 => {
+                    "the session cookie was refused, it has probably expired".to_string()
+                }
+                _ => format!("the server answered with status {}", status),
+            }));
+        }
+
+        let outcome = Outcome::parse(&page).ok_or_else(|| {
+            Error::Remote("couldn't find a verdict in the server's response".to_string())
+        })?;
+
+        self.attempts.record(Attempt {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            submitted_at: now,
+        })?;
+
+        Ok(outcome)
+    }
+}
+
+#[test]
+fn test_parse_outcome() {
+    assert_eq!(
+        Outcome::parse(
+            "<article><p>That's the right answer! You are one gold star closer.</p></article>"
+        ),
+        Some(Outcome::Correct)
+    );
+    assert_eq!(
+        Outcome::parse(
+            "<p>That's not the right answer; your answer is too high.  If you're stuck, ...</p>"
+        ),
+        Some(Outcome::TooHigh)
+    );
+    assert_eq!(
+        Outcome::parse("<p>That's not the right answer; your answer is too low.</p>"),
+        Some(Outcome::TooLow)
+    );
+    assert_eq!(
+        Outcome::parse("<p>That's not the right answer.  If you're stuck, ...</p>"),
+        Some(Outcome::Wrong)
+    );
+    assert_eq!(
+        Outcome::parse("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p>"),
+        Some(Outcome::Wait(Duration::from_secs(65)))
+    );
+    assert_eq!(
+        Outcome::parse(
+            "<p>You don't seem to be solving the right level.  Did you already complete it?</p>"
+        ),
+        Some(Outcome::AlreadySolved)
+    );
+    assert_eq!(Outcome::parse("<p>Something else</p>"), None);
+}
+
+#[test]
+fn test_form_encode() {
+    assert_eq!(form_encode("-12"), "-12");
+    assert_eq!(form_encode("a b&c"), "a%20b%26c");
+}
+
+#[cfg(test)]
+fn answer_server(method: &str, url: &str, _: &str, body: &str) -> (u16, String) {
+    let verdict = match (method, url, body) {
+        ("POST", "/2023/day/1/answer", "level=1&answer=75") => "That's the right answer!",
+        ("POST", "/2023/day/1/answer", "level=1&answer=100") => {
+            "That's not the right answer; your answer is too high."
+        }
+        ("POST", "/2023/day/1/answer", "level=1&answer=50") => {
+            "That's not the right answer; your answer is too low."
+        }
+        ("POST", "/2023/day/1/answer", "level=2&answer=7") => {
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 35s left to wait."
+        }
+        ("POST", "/2023/day/1/answer", _) => "That's not the right answer.",
+        _ => return (404, "404 Not Found".to_string()),
+    };
+
+    (
+        200,
+        format!("<main><article><p>{}</p></article></main>", verdict),
+    )
+}
+
+#[test]
+fn test_submit() {
+    let (url, requests) = crate::fetch::mock_server(answer_server);
+    let path = std::env::temp_dir().join(format!("aoc-attempts-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let client = Client::new(&url, "good", "input");
+    let mut submitter = Submitter::new(client, Attempts::load(&path).unwrap());
+
+    let refused = |reason: &str| Err(Error::Invalid(format!("not submitting: {}", reason)));
+
+    assert_eq!(submitter.submit(2023, 1, 1, "100"), Ok(Outcome::TooHigh));
+    assert_eq!(
+        submitter.submit(2023, 1, 1, "100"),
+        refused("100 was already rejected as too high")
+    );
+    assert_eq!(
+        submitter.submit(2023, 1, 1, "150"),
+        refused("150 can't be right, 100 was already too high")
+    );
+
+    assert_eq!(submitter.submit(2023, 1, 1, "50"), Ok(Outcome::TooLow));
+    assert_eq!(
+        submitter.submit(2023, 1, 1, "-3"),
+        refused("-3 can't be right, 50 was already too low")
+    );
+
+    assert_eq!(submitter.submit(2023, 1, 1, "75"), Ok(Outcome::Correct));
+    assert_eq!(
+        submitter.submit(2023, 1, 1, "76"),
+        refused("part 1 of day 1 of 2023 was already solved with 75")
+    );
+
+    assert_eq!(
+        submitter.submit(2023, 1, 2, "7"),
+        Ok(Outcome::Wait(Duration::from_secs(35)))
+    );
+    assert!(submitter.submit(2023, 1, 2, "8").is_err());
+
+    assert_eq!(requests.lock().unwrap().len(), 4);
+
+    // Every attempt survives a reload
+    let reloaded = Attempts::load(&path).unwrap();
+    assert_eq!(&reloaded, submitter.attempts());
+    assert_eq!(reloaded.for_puzzle(2023, 1, 1).count(), 3);
+
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,40 @@
+//! The solvers for the browser. Build them with
+//!
+//! ```sh
+//! cargo build --release --target wasm32-unknown-unknown --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir web/pkg target/wasm32-unknown-unknown/release/advent_of_code.wasm
+//! ```
+//!
+//! then serve `web/` and open `index.html`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::registry::{self, SOLUTIONS};
+
+/// Solves one part of a puzzle from the most recent year, throwing the reason
+/// when there is no answer.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    solve_year(registry::latest_year(), day, part, input)
+}
+
+/// Like [`solve`], for any year.
+#[wasm_bindgen(js_name = solveYear)]
+pub fn solve_year(year: u16, day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solution = registry::find(year, day, part)
+        .ok_or_else(|| format!("{}/{}/{} has no solver", year, day, part))?;
+
+    solution.solve(input).map_err(|e| e.to_string())
+}
+
+/// Every day with a solver, like `2023/5`, ordered by year and day.
+#[wasm_bindgen]
+pub fn days() -> Vec<String> {
+    let mut days = SOLUTIONS
+        .iter()
+        .map(|s| format!("{}/{}", s.year, s.day))
+        .collect::<Vec<_>>();
+
+    days.dedup();
+    days
+}
@@ -0,0 +1,185 @@
+use bstr::ByteSlice;
+
+use crate::{Error, Result};
+
+const DIGIT_0: u8 = b'0';
+const DIGIT_9: u8 = b'9';
+
+/// Adds up the calibration value of every line: the first digit `digit` finds
+/// in it, followed by the last.
+fn calibration(input: &[&[u8]], digit: impl Fn(&[u8]) -> Option<u8>) -> Result<u64> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let first = (0..line.len())
+                .find_map(|pos| digit(&line[pos..]))
+                .ok_or_else(|| Error::parse(idx + 1, 1, "line contains no digits"))?;
+            let last = (0..line.len())
+                .rev()
+                .find_map(|pos| digit(&line[pos..]))
+                .unwrap();
+
+            Ok((first as u64) * 10 + (last as u64))
+        })
+        .sum()
+}
+
+fn ascii_digit(input: &[u8]) -> Option<u8> {
+    (input[0] >= DIGIT_0 && input[0] <= DIGIT_9).then(|| input[0] - DIGIT_0)
+}
+
+/// A digit, either written as one or spelled out.
+fn digit(input: &[u8]) -> Option<u8> {
+    if let Some(d) = ascii_digit(input) {
+        Some(d)
+    } else if input.starts_with_str("one") {
+        Some(1)
+    } else if input.starts_with_str("two") {
+        Some(2)
+    } else if input.starts_with_str("three") {
+        Some(3)
+    } else if input.starts_with_str("four") {
+        Some(4)
+    } else if input.starts_with_str("five") {
+        Some(5)
+    } else if input.starts_with_str("six") {
+        Some(6)
+    } else if input.starts_with_str("seven") {
+        Some(7)
+    } else if input.starts_with_str("eight") {
+        Some(8)
+    } else if input.starts_with_str("nine") {
+        Some(9)
+    } else {
+        None
+    }
+}
+
+/// Every line's bytes, borrowed from the input.
+pub fn generator(input: &str) -> Vec<&[u8]> {
+    input.split('\n').map(str::as_bytes).collect()
+}
+
+pub fn part1(input: &[&[u8]]) -> Result<u64> {
+    calibration(input, ascii_digit)
+}
+
+pub fn part2(input: &[&[u8]]) -> Result<u64> {
+    calibration(input, digit)
+}
+
+// cargo-aoc keeps what a generator returns past the input's lifetime, so it
+// parses inside each part instead
+#[cfg(feature = "runner")]
+#[aoc(day1, part1)]
+fn aoc_part1(input: &str) -> Result<u64> {
+    part1(&generator(input))
+}
+
+#[cfg(feature = "runner")]
+#[aoc(day1, part2)]
+fn aoc_part2(input: &str) -> Result<u64> {
+    part2(&generator(input))
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        part1(&generator("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet")),
+        Ok(142)
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen")), Ok(281));
+}
+
+#[test]
+fn test_no_digits() {
+    assert_eq!(
+        part1(&generator("1abc2\npqrstu")),
+        Err(Error::parse(2, 1, "line contains no digits"))
+    );
+    assert_eq!(
+        part2(&generator("one\npqrstu")),
+        Err(Error::parse(2, 1, "line contains no digits"))
+    );
+}
+
+#[test]
+fn test_generator_borrows() {
+    let input = "1abc2\npqr3stu8vwx";
+    let lines = generator(input);
+
+    assert_eq!(lines, [&b"1abc2"[..], b"pqr3stu8vwx"]);
+    assert_eq!(lines[1].as_ptr(), input[6..].as_ptr());
+}
+
+#[test]
+fn test_no_allocations() {
+    // The registry trims the trailing newline before parsing
+    let input = crate::gen::generate(2023, 1, 1, &[]);
+    let input = generator(input.trim_end());
+    let (answers, allocations) = crate::memory::measure(|| (part1(&input), part2(&input)));
+
+    assert!(answers.0.is_ok() && answers.1.is_ok());
+    assert_eq!(allocations.map(|a| a.allocations), Some(0));
+}
+
+/// Lines built from digits, spelled digits and letters that let the words overlap.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, sample::select, strategy::Strategy};
+
+    const PIECES: &[&str] = &[
+        "1", "5", "9", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "e",
+        "n", "o", "t", "x",
+    ];
+
+    vec(
+        vec(select(PIECES), 1..12).prop_map(|pieces| pieces.concat()),
+        1..8,
+    )
+    .prop_map(|lines| lines.join("\n"))
+}
+
+/// Finds the first and last digits by searching for every way of writing each one.
+#[cfg(test)]
+fn calibration_brute_force(input: &str, words: bool) -> Option<u64> {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    let mut spellings = (1..=9u64).map(|d| (d.to_string(), d)).collect::<Vec<_>>();
+
+    if words {
+        spellings.extend((1..=9u64).map(|d| (WORDS[d as usize - 1].to_string(), d)));
+    }
+
+    input
+        .split('\n')
+        .map(|line| {
+            // None of the words overlap with themselves, so no occurrence is skipped
+            let found = spellings
+                .iter()
+                .flat_map(|(s, d)| line.match_indices(s.as_str()).map(move |(i, _)| (i, *d)))
+                .collect::<Vec<_>>();
+
+            let first = found.iter().min()?.1;
+            let last = found.iter().max()?.1;
+
+            Some(first * 10 + last)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        proptest::prop_assert_eq!(part1(&generator(&input)).ok(), calibration_brute_force(&input, false));
+        proptest::prop_assert_eq!(part2(&generator(&input)).ok(), calibration_brute_force(&input, true));
+    }
+}
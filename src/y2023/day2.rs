@@ -0,0 +1,440 @@
+use std::{borrow::Cow, fmt, str};
+
+use bstr::ByteSlice;
+
+use crate::{
+    parse::{self, Cursor, Rows},
+    Error, Result,
+};
+
+/// A number of cubes of each color, either a whole bag or a handful drawn from
+/// one. The colors are borrowed, so a bag written out in the code needs no
+/// allocation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag<'a>(Cow<'a, [(&'a str, u64)]>);
+
+/// The bag part 1 asks about.
+pub const ELF_BAG: Bag<'static> = Bag(Cow::Borrowed(&[("red", 12), ("green", 13), ("blue", 14)]));
+
+/// The colors the puzzle multiplies together in part 2.
+pub const PUZZLE_COLORS: [&str; 3] = ["red", "green", "blue"];
+
+impl<'a> Bag<'a> {
+    pub fn count(&self, color: &str) -> u64 {
+        self.0
+            .iter()
+            .find(|(c, _)| *c == color)
+            .map_or(0, |&(_, n)| n)
+    }
+
+    /// Whether all the cubes in `other` could have come out of this bag.
+    pub fn contains(&self, other: &Bag) -> bool {
+        other.0.iter().all(|&(color, n)| n <= self.count(color))
+    }
+
+    /// The number of cubes of each of `colors` in the bag, multiplied together.
+    /// A color the bag doesn't hold counts as 0.
+    pub fn power(&self, colors: &[&str]) -> Result<u64> {
+        product(colors.iter().map(|color| self.count(color)))
+    }
+
+    /// Parses a list of cubes like "3 blue, 4 red".
+    pub fn parse(input: &'a str) -> Result<Self> {
+        let set = Cursor::new(input.as_bytes());
+        let mut bag = Vec::new();
+
+        for cube in cubes(set) {
+            let (color, n) = cube?;
+
+            if bag.iter().any(|&(c, _)| c == color) {
+                return Err(set.error_at(color.as_bytes(), repeated_color(color)));
+            }
+
+            bag.push((color, n));
+        }
+
+        Ok(Bag(Cow::Owned(bag)))
+    }
+}
+
+impl fmt::Display for Bag<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut cubes = self.0.to_vec();
+        cubes.sort();
+
+        for (idx, (color, n)) in cubes.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{} {}", n, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The colors and counts in a list of cubes like "3 blue, 4 red".
+fn cubes<'a>(set: Cursor<'a>) -> impl Iterator<Item = Result<(&'a str, u64)>> {
+    set.split(b',').map(|mut cubes| {
+        let s = cubes.trim().rest();
+        let n = cubes
+            .until(b' ')
+            .ok_or_else(|| cubes.error_at(s, "expected \"<count> <color>\""))?;
+        let color = cubes.rest();
+
+        match str::from_utf8(color) {
+            Ok(color) if !color.is_empty() && color.chars().all(char::is_alphabetic) => {
+                Ok((color, cubes.parse_number(n)?))
+            }
+            _ => Err(cubes.error_at(color, format!("invalid cube color {:?}", color.as_bstr()))),
+        }
+    })
+}
+
+fn overflow() -> Error {
+    Error::Invalid("the answer doesn't fit in a u64".to_string())
+}
+
+fn product(mut counts: impl Iterator<Item = u64>) -> Result<u64> {
+    counts.try_fold(1u64, |product, n| {
+        product.checked_mul(n).ok_or_else(overflow)
+    })
+}
+
+fn repeated_color(color: &str) -> String {
+    format!("{} appears more than once in one set", color)
+}
+
+/// Every game in the input. Each color's name is stored once, and each game
+/// keeps only the fewest cubes of each color it needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Games {
+    ids: Vec<u64>,
+    /// Every color named in any game, in the order they first appear.
+    colors: Vec<String>,
+    /// For each game, the fewest cubes of each color it needs, indexed like
+    /// `colors`. Colors first named after a game count 0 for it.
+    minimums: Rows<u64>,
+}
+
+impl Games {
+    fn color(&mut self, color: &str) -> usize {
+        match self.colors.iter().position(|c| c == color) {
+            Some(idx) => idx,
+            None => {
+                self.colors.push(color.to_string());
+                self.colors.len() - 1
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Game<'_>> {
+        self.ids
+            .iter()
+            .zip(self.minimums.iter())
+            .map(|(&id, minimums)| Game {
+                id,
+                colors: &self.colors,
+                minimums,
+            })
+    }
+}
+
+/// One game, borrowed from [`Games`].
+#[derive(Debug, Clone, Copy)]
+pub struct Game<'a> {
+    pub id: u64,
+    colors: &'a [String],
+    minimums: &'a [u64],
+}
+
+impl Game<'_> {
+    fn cubes(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.colors
+            .iter()
+            .zip(self.minimums)
+            .map(|(color, &n)| (color.as_str(), n))
+    }
+
+    /// The fewest cubes of `color` this game could have been played with.
+    pub fn count(&self, color: &str) -> u64 {
+        self.cubes()
+            .find(|&(c, _)| c == color)
+            .map_or(0, |(_, n)| n)
+    }
+
+    /// Whether every set in this game could have been drawn from `bag`.
+    pub fn fits_in(&self, bag: &Bag) -> bool {
+        self.cubes().all(|(color, n)| n <= bag.count(color))
+    }
+
+    /// The smallest bag that every set in this game could have been drawn from.
+    pub fn minimum_cubes_needed(&self) -> Bag<'_> {
+        Bag(Cow::Owned(self.cubes().filter(|&(_, n)| n > 0).collect()))
+    }
+
+    /// Like [`Bag::power`], for the smallest bag this game could have been played with.
+    pub fn power(&self, colors: &[&str]) -> Result<u64> {
+        product(colors.iter().map(|color| self.count(color)))
+    }
+}
+
+/// Sums the ids of the games that could have been played with `bag`.
+pub fn possible_games(games: &Games, bag: &Bag) -> Result<u64> {
+    games
+        .iter()
+        .filter(|game| game.fits_in(bag))
+        .try_fold(0u64, |total, game| {
+            total.checked_add(game.id).ok_or_else(overflow)
+        })
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day2))]
+pub fn generator(input: &str) -> Result<Games> {
+    let mut games = Games::default();
+    // The set each color was last drawn in, to catch a color drawn twice in one set
+    let mut last_drawn = Vec::new();
+    let mut sets = 0;
+
+    for mut line in parse::lines(input.as_bytes()) {
+        line.expect("Game ")?;
+
+        let id = line
+            .until(b':')
+            .ok_or_else(|| line.error("expected \":\" after the game id"))?;
+        games.ids.push(line.parse_number(id)?);
+
+        for _ in 0..games.colors.len() {
+            games.minimums.push(0);
+        }
+
+        for set in line.split(b';') {
+            sets += 1;
+
+            for cube in cubes(set) {
+                let (color, n) = cube?;
+                let idx = games.color(color);
+
+                if idx == last_drawn.len() {
+                    last_drawn.push(0);
+                    games.minimums.push(0);
+                }
+
+                if last_drawn[idx] == sets {
+                    return Err(set.error_at(color.as_bytes(), repeated_color(color)));
+                }
+
+                last_drawn[idx] = sets;
+
+                let most = &mut games.minimums.current_mut()[idx];
+                *most = (*most).max(n);
+            }
+        }
+
+        games.minimums.end_row();
+    }
+
+    Ok(games)
+}
+
+#[cfg_attr(feature = "runner", aoc(day2, part1))]
+pub fn part1(input: &Games) -> Result<u64> {
+    possible_games(input, &ELF_BAG)
+}
+
+#[cfg_attr(feature = "runner", aoc(day2, part2))]
+pub fn part2(input: &Games) -> Result<u64> {
+    input.iter().try_fold(0u64, |total, game| {
+        total
+            .checked_add(game.power(&PUZZLE_COLORS)?)
+            .ok_or_else(overflow)
+    })
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&generator("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green").unwrap()), Ok(8));
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green").unwrap()), Ok(2286));
+}
+
+#[test]
+fn test_parse_error() {
+    use crate::Error;
+
+    assert_eq!(
+        generator("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2").err(),
+        Some(Error::parse(2, 17, "expected \"<count> <color>\""))
+    );
+
+    assert_eq!(
+        generator("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 r3d").err(),
+        Some(Error::parse(2, 19, "invalid cube color \"r3d\""))
+    );
+
+    assert_eq!(
+        generator("Game 1: 18446744073709551615 red, 1 red").err(),
+        Some(Error::parse(1, 37, "red appears more than once in one set"))
+    );
+
+    assert_eq!(
+        generator("Game 1: 3 blue;").err(),
+        Some(Error::parse(1, 16, "expected \"<count> <color>\""))
+    );
+}
+
+#[test]
+fn test_custom_bag() {
+    let games = generator("Game 1: 3 blue, 4 red; 2 purple\nGame 2: 1 blue, 5 purple; 3 green, 4 blue, 1 red\nGame 3: 9 green, 1 red").unwrap();
+
+    let bag = Bag::parse("4 red, 4 blue, 3 purple, 9 green").unwrap();
+
+    assert_eq!(bag.count("purple"), 3);
+    assert_eq!(possible_games(&games, &bag), Ok(1 + 3));
+    assert_eq!(
+        games
+            .iter()
+            .nth(1)
+            .unwrap()
+            .minimum_cubes_needed()
+            .to_string(),
+        "4 blue, 3 green, 5 purple, 1 red"
+    );
+    assert_eq!(part2(&games), Ok(12));
+    assert_eq!(bag.power(&["red", "purple"]), Ok(12));
+    assert_eq!(bag.power(&PUZZLE_COLORS), Ok(144));
+}
+
+#[test]
+fn test_many_colors() {
+    let games =
+        generator("Game 1: 1 a, 1 b, 1 c, 1 d; 1 e, 1 f, 1 g, 1 h; 1 i\nGame 2: 2 i, 3 a; 4 j")
+            .unwrap();
+    let colors = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+
+    assert_eq!(games.iter().next().unwrap().power(&colors), Ok(1));
+    assert_eq!(games.iter().nth(1).unwrap().power(&["a", "i", "j"]), Ok(24));
+    assert_eq!(games.iter().nth(1).unwrap().count("b"), 0);
+    assert_eq!(part2(&games), Ok(0));
+}
+
+#[test]
+fn test_overflow() {
+    let games = generator("Game 1: 18446744073709551615 red, 2 blue, 2 green").unwrap();
+
+    assert_eq!(part2(&games), Err(overflow()));
+
+    let games = generator("Game 18446744073709551615: 1 red\nGame 1: 1 red").unwrap();
+
+    assert_eq!(part1(&games), Err(overflow()));
+}
+
+#[test]
+fn test_power_of_missing_color() {
+    // No green cubes were drawn, so there may have been none in the bag
+    assert_eq!(
+        part2(&generator("Game 1: 3 red, 4 blue; 1 red").unwrap()),
+        Ok(0)
+    );
+    assert_eq!(
+        part2(&generator("Game 1: 3 red, 4 blue, 0 green").unwrap()),
+        Ok(0)
+    );
+    assert_eq!(
+        part2(&generator("Game 1: 3 red, 4 blue; 2 green").unwrap()),
+        Ok(24)
+    );
+}
+
+#[test]
+fn test_no_allocations() {
+    let input = generator(crate::gen::generate(2023, 2, 1, &[]).trim_end()).unwrap();
+    let (_, allocations) = crate::memory::measure(|| (part1(&input), part2(&input)));
+
+    assert_eq!(allocations.map(|a| a.allocations), Some(0));
+}
+
+/// Games with a few draws each, where a draw shows each color at most once.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, sample::subsequence, strategy::Strategy};
+
+    let set =
+        (subsequence(&PUZZLE_COLORS[..], 1..=3), vec(0u64..20, 3)).prop_map(|(colors, counts)| {
+            colors
+                .iter()
+                .zip(counts)
+                .map(|(color, n)| format!("{} {}", n, color))
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+    let game = vec(set, 1..5).prop_map(|sets| sets.join("; "));
+
+    vec(game, 1..10).prop_map(|games| {
+        games
+            .iter()
+            .enumerate()
+            .map(|(idx, game)| format!("Game {}: {}", idx + 1, game))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// Finds the most cubes of each color drawn in each game, straight from the text.
+#[cfg(test)]
+fn cubes_brute_force(input: &str) -> (u64, u64) {
+    let mut possible = 0;
+    let mut power = 0;
+
+    for line in input.lines() {
+        let (id, sets) = line["Game ".len()..].split_once(": ").unwrap();
+        let mut most = std::collections::BTreeMap::<&str, u64>::new();
+        let mut fits = true;
+
+        for set in sets.split("; ") {
+            for cubes in set.split(", ") {
+                let (n, color) = cubes.split_once(' ').unwrap();
+                let n = n.parse::<u64>().unwrap();
+
+                let limit = match color {
+                    "red" => 12,
+                    "green" => 13,
+                    _ => 14,
+                };
+
+                fits &= n <= limit;
+
+                let most = most.entry(color).or_default();
+                *most = (*most).max(n);
+            }
+        }
+
+        if fits {
+            possible += id.parse::<u64>().unwrap();
+        }
+
+        power += PUZZLE_COLORS
+            .iter()
+            .map(|color| most.get(color).copied().unwrap_or(0))
+            .product::<u64>();
+    }
+
+    (possible, power)
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let (possible, power) = cubes_brute_force(&input);
+
+        let games = generator(&input).unwrap();
+
+        proptest::prop_assert_eq!(part1(&games), Ok(possible));
+        proptest::prop_assert_eq!(part2(&games), Ok(power));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    grid::{Direction, Grid, Point},
+    Error, Result,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Cell {
+    Empty,
+    Symbol(char),
+    Digit(u8),
+}
+
+impl Cell {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '0' => Some(Cell::Digit(0)),
+            '1' => Some(Cell::Digit(1)),
+            '2' => Some(Cell::Digit(2)),
+            '3' => Some(Cell::Digit(3)),
+            '4' => Some(Cell::Digit(4)),
+            '5' => Some(Cell::Digit(5)),
+            '6' => Some(Cell::Digit(6)),
+            '7' => Some(Cell::Digit(7)),
+            '8' => Some(Cell::Digit(8)),
+            '9' => Some(Cell::Digit(9)),
+            '-' => Some(Cell::Symbol('-')),
+            '@' => Some(Cell::Symbol('@')),
+            '*' => Some(Cell::Symbol('*')),
+            '/' => Some(Cell::Symbol('/')),
+            '&' => Some(Cell::Symbol('&')),
+            '#' => Some(Cell::Symbol('#')),
+            '%' => Some(Cell::Symbol('%')),
+            '+' => Some(Cell::Symbol('+')),
+            '=' => Some(Cell::Symbol('=')),
+            '$' => Some(Cell::Symbol('$')),
+            _ => None,
+        }
+    }
+}
+
+/// Whether any of the eight cells surrounding `point` holds a symbol.
+fn has_symbol_adjacent(schematic: &Grid<Cell>, point: Point) -> bool {
+    schematic
+        .neighbours8(point)
+        .any(|(_, cell)| matches!(cell, Cell::Symbol(_)))
+}
+
+fn overflow() -> Error {
+    Error::Invalid("the part numbers don't fit in a u64".to_string())
+}
+
+/// Appends digit `d` to the number `n`.
+fn push_digit(n: u64, d: u8) -> Result<u64> {
+    n.checked_mul(10)
+        .and_then(|n| n.checked_add(d as u64))
+        .ok_or_else(overflow)
+}
+
+/// Reads the whole part number that has a digit at `point`, returning where it starts along with its value.
+fn read_part_number(schematic: &Grid<Cell>, mut point: Point) -> Result<(Point, u64)> {
+    while let Some(Cell::Digit(_)) = schematic.get(point.step(Direction::West)) {
+        point = point.step(Direction::West);
+    }
+
+    let start = point;
+    let mut n = 0;
+
+    while let Some(Cell::Digit(d)) = schematic.get(point) {
+        n = push_digit(n, *d)?;
+        point = point.step(Direction::East);
+    }
+
+    Ok((start, n))
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day3))]
+pub fn generator(input: &str) -> Result<Grid<Cell>> {
+    Grid::parse(input, Cell::from_char)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ParserState {
+    Empty,
+    PartNumber(bool, u64),
+}
+
+#[cfg_attr(feature = "runner", aoc(day3, part1))]
+pub fn part1(schematic: &Grid<Cell>) -> Result<u64> {
+    let mut sum = 0;
+
+    for y in 0..schematic.height() as isize {
+        let mut state = ParserState::Empty;
+
+        for x in 0..=schematic.width() as isize {
+            let point = Point::new(x, y);
+
+            state = match (state, schematic.get(point).unwrap_or(&Cell::Empty)) {
+                (ParserState::Empty, Cell::Digit(n)) => {
+                    ParserState::PartNumber(has_symbol_adjacent(schematic, point), *n as u64)
+                }
+                (ParserState::PartNumber(has_symbol_adjacent_so_far, n), Cell::Digit(m)) => {
+                    ParserState::PartNumber(
+                        has_symbol_adjacent_so_far || has_symbol_adjacent(schematic, point),
+                        push_digit(n, *m)?,
+                    )
+                }
+                (ParserState::PartNumber(has_symbol_adjacent, n), _) => {
+                    if has_symbol_adjacent {
+                        sum = n.checked_add(sum).ok_or_else(overflow)?;
+                    }
+
+                    ParserState::Empty
+                }
+                (ParserState::Empty, _) => ParserState::Empty,
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
+#[cfg_attr(feature = "runner", aoc(day3, part2))]
+pub fn part2(schematic: &Grid<Cell>) -> Result<u64> {
+    let mut sum = 0;
+
+    for (point, cell) in schematic.iter() {
+        if *cell != Cell::Symbol('*') {
+            continue;
+        }
+
+        let mut part_numbers = schematic
+            .neighbours8(point)
+            .filter(|(_, cell)| matches!(cell, Cell::Digit(_)))
+            .map(|(p, _)| read_part_number(schematic, p))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Several digits of the same number can touch the gear
+        part_numbers.sort();
+        part_numbers.dedup();
+
+        if part_numbers.len() == 2 {
+            sum = part_numbers[0]
+                .1
+                .checked_mul(part_numbers[1].1)
+                .and_then(|ratio| ratio.checked_add(sum))
+                .ok_or_else(overflow)?;
+        }
+    }
+
+    Ok(sum)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&generator("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..").unwrap()), Ok(4361));
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..").unwrap()), Ok(467835));
+}
+
+#[test]
+fn test_invalid_cell() {
+    use crate::Error;
+
+    assert_eq!(
+        generator("467..114..\n...?......").err(),
+        Some(Error::parse(2, 4, "unexpected character '?'"))
+    );
+}
+
+#[test]
+fn test_overflow() {
+    let schematic = generator("99999999999999999999*").unwrap();
+
+    assert_eq!(part1(&schematic), Err(overflow()));
+    assert_eq!(part2(&schematic), Err(overflow()));
+
+    let schematic = generator("4294967296*4294967296").unwrap();
+
+    assert_eq!(part1(&schematic), Ok(2 * 4294967296));
+    assert_eq!(part2(&schematic), Err(overflow()));
+}
+
+/// Rectangular schematics, mostly empty, narrow enough that gear ratios fit in a `u64`.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, sample::select, strategy::Strategy};
+
+    const CELLS: &[char] = &['.', '.', '.', '.', '1', '2', '5', '9', '0', '*', '*', '#'];
+
+    (1..10usize, 1..8usize).prop_flat_map(|(width, height)| {
+        vec(vec(select(CELLS), width), height).prop_map(|rows| {
+            rows.iter()
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    })
+}
+
+/// Finds every run of digits, then checks each cell around it one by one.
+#[cfg(test)]
+fn schematic_brute_force(input: &str) -> (u64, u64) {
+    let rows = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Every number as its row, first and last column, and value
+    let mut numbers = Vec::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+
+        while x < row.len() {
+            let start = x;
+
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+
+            if x > start {
+                let value = row[start..x]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<u64>()
+                    .unwrap();
+                numbers.push((y as isize, start as isize, x as isize - 1, value));
+            } else {
+                x += 1;
+            }
+        }
+    }
+
+    let touches = |&(y, first, last, _): &(isize, isize, isize, u64), (cy, cx): (isize, isize)| {
+        (y - 1..=y + 1).contains(&cy) && (first - 1..=last + 1).contains(&cx)
+    };
+
+    let cells = rows.iter().enumerate().flat_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .map(move |(x, &c)| ((y as isize, x as isize), c))
+    });
+
+    let symbols = cells
+        .filter(|&(_, c)| c != '.' && !c.is_ascii_digit())
+        .collect::<Vec<_>>();
+
+    let part_numbers = numbers
+        .iter()
+        .filter(|number| symbols.iter().any(|&(cell, _)| touches(number, cell)))
+        .map(|number| number.3)
+        .sum();
+
+    let gear_ratios = symbols
+        .iter()
+        .filter(|&&(_, c)| c == '*')
+        .map(|&(cell, _)| {
+            let adjacent = numbers
+                .iter()
+                .filter(|number| touches(number, cell))
+                .collect::<Vec<_>>();
+
+            match adjacent[..] {
+                [a, b] => a.3 * b.3,
+                _ => 0,
+            }
+        })
+        .sum();
+
+    (part_numbers, gear_ratios)
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let schematic = generator(&input).unwrap();
+        let (part_numbers, gear_ratios) = schematic_brute_force(&input);
+
+        proptest::prop_assert_eq!(part1(&schematic), Ok(part_numbers));
+        proptest::prop_assert_eq!(part2(&schematic), Ok(gear_ratios));
+    }
+}
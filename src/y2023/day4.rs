@@ -0,0 +1,300 @@
+use crate::{
+    parse::{self, Cursor},
+    Error, Result,
+};
+
+/// A set of card numbers, which all fit in a `u8`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Numbers([u128; 2]);
+
+impl Numbers {
+    fn insert(&mut self, n: u8) {
+        self.0[n as usize / 128] |= 1 << (n % 128);
+    }
+
+    fn intersection_len(&self, other: &Numbers) -> usize {
+        (0..2)
+            .map(|i| (self.0[i] & other.0[i]).count_ones() as usize)
+            .sum()
+    }
+
+    fn parse(mut cursor: Cursor) -> Result<Self> {
+        let mut numbers = Numbers::default();
+
+        loop {
+            let token = cursor.token();
+
+            if token.is_empty() {
+                return Ok(numbers);
+            }
+
+            let n = u8::try_from(cursor.parse_number::<u64>(token)?)
+                .map_err(|_| cursor.error_at(token, "card numbers must be below 256"))?;
+            numbers.insert(n);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    id: u64,
+    our_numbers: Numbers,
+    winning_numbers: Numbers,
+}
+
+impl Card {
+    fn matching_numbers(&self) -> usize {
+        self.our_numbers.intersection_len(&self.winning_numbers)
+    }
+
+    fn points(&self) -> Result<u64> {
+        match self.matching_numbers() {
+            0 => Ok(0),
+            n => 1u64.checked_shl(n as u32 - 1).ok_or_else(|| {
+                Error::Invalid(format!(
+                    "card {} is worth more points than fit in a u64",
+                    self.id
+                ))
+            }),
+        }
+    }
+
+    pub fn parse(mut line: Cursor) -> Result<Self> {
+        line.expect("Card")?;
+
+        let id = line
+            .until(b':')
+            .ok_or_else(|| line.error("expected \":\" after the card id"))?;
+        let id = line.parse_number::<u64>(id.trim_ascii())?;
+
+        line.trim();
+        let winning_numbers = line
+            .until(b'|')
+            .ok_or_else(|| line.error("expected \"|\" between the numbers"))?;
+
+        Ok(Card {
+            id,
+            winning_numbers: Numbers::parse(line.at(winning_numbers))?,
+            our_numbers: Numbers::parse(line)?,
+        })
+    }
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day4))]
+pub fn generator(input: &str) -> Result<Vec<Card>> {
+    parse::lines(input.as_bytes()).map(Card::parse).collect()
+}
+
+#[cfg_attr(feature = "runner", aoc(day4, part1))]
+pub fn part1(input: &[Card]) -> Result<u64> {
+    input.iter().try_fold(0u64, |total, card| {
+        total
+            .checked_add(card.points()?)
+            .ok_or_else(|| Error::Invalid("the points don't fit in a u64".to_string()))
+    })
+}
+
+fn too_many_cards() -> Error {
+    Error::Invalid("the number of cards doesn't fit in a usize".to_string())
+}
+
+/// There are only 256 different numbers, so a card wins copies of at most the
+/// next 256 cards.
+const MOST_MATCHES: usize = 256;
+
+#[cfg_attr(feature = "runner", aoc(day4, part2))]
+pub fn part2(input: &[Card]) -> Result<usize> {
+    // The copies won of each of the next few cards, indexed by card id
+    let mut copies = [0usize; MOST_MATCHES + 1];
+    let mut previous_id = None;
+    let mut total = 0usize;
+    let slot = |id: u64| (id % (MOST_MATCHES as u64 + 1)) as usize;
+
+    for card in input {
+        if let Some(previous_id) = previous_id {
+            if card.id <= previous_id {
+                return Err(Error::Invalid(format!(
+                    "card {} follows card {}, but the card ids must increase",
+                    card.id, previous_id
+                )));
+            }
+
+            // Copies of cards that aren't in the table don't count
+            for skipped in (previous_id + 1..card.id).take(copies.len()) {
+                copies[slot(skipped)] = 0;
+            }
+        }
+
+        previous_id = Some(card.id);
+
+        let count = std::mem::take(&mut copies[slot(card.id)])
+            .checked_add(1)
+            .ok_or_else(too_many_cards)?;
+        let last = card
+            .id
+            .checked_add(card.matching_numbers() as u64)
+            .ok_or_else(|| {
+                Error::Invalid(format!(
+                    "card {} wins copies of cards past the last id",
+                    card.id
+                ))
+            })?;
+
+        for next in card.id + 1..=last {
+            copies[slot(next)] = copies[slot(next)]
+                .checked_add(count)
+                .ok_or_else(too_many_cards)?;
+        }
+
+        total = total.checked_add(count).ok_or_else(too_many_cards)?;
+    }
+
+    Ok(total)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&generator("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap()), Ok(13));
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap()), Ok(30));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        generator("Card 1: 1 | 1\nCard 2 1 | 1").err(),
+        Some(Error::parse(2, 5, "expected \":\" after the card id"))
+    );
+    assert_eq!(
+        generator("Card 1: 1 1").err(),
+        Some(Error::parse(1, 9, "expected \"|\" between the numbers"))
+    );
+    assert_eq!(
+        generator("Card 1: 1 256 | 1").err(),
+        Some(Error::parse(1, 11, "card numbers must be below 256"))
+    );
+    assert_eq!(
+        generator("Card 1: 1 | 255 1000").err(),
+        Some(Error::parse(1, 17, "card numbers must be below 256"))
+    );
+    assert_eq!(
+        generator("Card 1: 1 -1 | 1").err(),
+        Some(Error::parse(1, 11, "expected a number, found \"-1\""))
+    );
+    assert_eq!(
+        part2(&generator("Card 2: 1 | 1\nCard 2: 1 | 1").unwrap()).err(),
+        Some(Error::Invalid(
+            "card 2 follows card 2, but the card ids must increase".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_overflow() {
+    let numbers = (0..65).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+    let cards = generator(&format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+
+    assert_eq!(
+        part1(&cards),
+        Err(Error::Invalid(
+            "card 1 is worth more points than fit in a u64".to_string()
+        ))
+    );
+
+    let cards = generator("Card 18446744073709551615: 1 | 1").unwrap();
+
+    assert_eq!(part1(&cards), Ok(1));
+    assert_eq!(
+        part2(&cards),
+        Err(Error::Invalid(
+            "card 18446744073709551615 wins copies of cards past the last id".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_part2_skipped_cards() {
+    // Card 1 wins a copy of card 2, which isn't in the table
+    assert_eq!(
+        part2(&generator("Card 1: 1 | 1\nCard 3: 2 | 3").unwrap()),
+        Ok(2)
+    );
+}
+
+#[test]
+fn test_no_allocations() {
+    let input = generator(crate::gen::generate(2023, 4, 1, &[]).trim_end()).unwrap();
+    let (answers, allocations) = crate::memory::measure(|| (part1(&input), part2(&input)));
+
+    assert!(answers.0.is_ok() && answers.1.is_ok());
+    assert_eq!(allocations.map(|a| a.allocations), Some(0));
+}
+
+/// Tables of cards numbered from 1, each with distinct winning numbers.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{
+        collection::{btree_set, vec},
+        strategy::Strategy,
+    };
+
+    let numbers = |len| {
+        btree_set(1u8..30, len).prop_map(|numbers| {
+            numbers
+                .iter()
+                .map(|n| format!("{:2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    };
+
+    vec((numbers(1..6), numbers(1..10)), 1..10).prop_map(|cards| {
+        cards
+            .iter()
+            .enumerate()
+            .map(|(idx, (winning, ours))| format!("Card {}: {} | {}", idx + 1, winning, ours))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// Plays the cascade one card at a time: every card, original or copy, wins a
+/// copy of each of the next few cards that exist.
+#[cfg(test)]
+fn card_count_brute_force(input: &str) -> usize {
+    let matches = input
+        .lines()
+        .map(|line| {
+            let (winning, ours) = line.split_once(": ").unwrap().1.split_once(" | ").unwrap();
+            let winning = winning.split_whitespace().collect::<Vec<_>>();
+
+            ours.split_whitespace()
+                .filter(|n| winning.contains(n))
+                .count()
+        })
+        .collect::<Vec<_>>();
+
+    let mut pile = (0..matches.len()).collect::<Vec<_>>();
+    let mut count = 0;
+
+    while let Some(card) = pile.pop() {
+        count += 1;
+        pile.extend((card + 1..=card + matches[card]).filter(|&next| next < matches.len()));
+    }
+
+    count
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_part2_matches_brute_force(input in arbitrary_input()) {
+        proptest::prop_assert_eq!(
+            part2(&generator(&input).unwrap()),
+            Ok(card_count_brute_force(&input))
+        );
+    }
+}
@@ -0,0 +1,903 @@
+use std::{fmt, str::FromStr};
+
+use crate::{error, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SeedRange {
+    pub start: u64,
+    pub len: u64,
+}
+
+impl SeedRange {
+    /// The largest number in the range, which must not be empty. Unlike the end
+    /// just past it, this fits in a `u64` even for a range that reaches `u64::MAX`.
+    pub fn last(&self) -> u64 {
+        self.start + (self.len - 1)
+    }
+
+    pub fn intersection(&self, other: &SeedRange) -> Option<SeedRange> {
+        if self.len == 0 || other.len == 0 {
+            return None;
+        }
+
+        let start = self.start.max(other.start);
+        let last = self.last().min(other.last());
+
+        (start <= last).then(|| SeedRange {
+            start,
+            len: last - start + 1,
+        })
+    }
+}
+
+/// Sorts `ranges` and joins the ones that overlap or touch. A range can hold
+/// at most `u64::MAX` numbers, so one covering every `u64` stops short of the last.
+fn merge_ranges(mut ranges: Vec<SeedRange>) -> Vec<SeedRange> {
+    ranges.retain(|range| range.len > 0);
+    ranges.sort();
+
+    let mut result = Vec::<SeedRange>::with_capacity(ranges.len());
+
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if last.last().saturating_add(1) >= range.start => {
+                last.len = (last.last().max(range.last()) - last.start).saturating_add(1);
+            }
+            _ => result.push(range),
+        }
+    }
+
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRange {
+    dst_start: u64,
+    src_start: u64,
+    len: u64,
+}
+
+impl MapRange {
+    fn src_end(&self) -> u64 {
+        self.src_start + self.len
+    }
+
+    #[cfg(test)]
+    fn source_intersection(&self, other: &SeedRange) -> Option<SeedRange> {
+        let start = other.start.max(self.src_start);
+        let end = (other.start + other.len).min(self.src_start + self.len);
+
+        if start < end {
+            Some(SeedRange {
+                start,
+                len: end - start,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for MapRange {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let expected = || Error::at(input, input, "expected \"<dst> <src> <len>\"");
+
+        let (dst_start, s) = input.split_once(' ').ok_or_else(expected)?;
+        let (src_start, len) = s.split_once(' ').ok_or_else(expected)?;
+
+        let dst_start = error::number::<u64>(input, dst_start)?;
+        let src_start = error::number::<u64>(input, src_start)?;
+        let len = error::number::<u64>(input, len)?;
+
+        if src_start.max(dst_start).checked_add(len).is_none() {
+            return Err(Error::at(
+                input,
+                input,
+                "the range runs past the largest number",
+            ));
+        }
+
+        Ok(MapRange {
+            dst_start,
+            src_start,
+            len,
+        })
+    }
+}
+
+impl fmt::Display for MapRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.dst_start, self.src_start, self.len)
+    }
+}
+
+/// A piecewise-linear function from sources to destinations, kept as sorted,
+/// non-overlapping ranges. Numbers outside of every range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map(Vec<MapRange>);
+
+impl Map {
+    /// Builds a map from ranges as listed in the almanac, where the first range
+    /// to cover a number is the one that applies to it.
+    fn from_ranges(ranges: impl IntoIterator<Item = MapRange>) -> Self {
+        let mut result = Vec::<MapRange>::new();
+
+        for range in ranges {
+            let mut uncovered = vec![(range.src_start, range.src_end())];
+
+            for earlier in &result {
+                uncovered = uncovered
+                    .into_iter()
+                    .flat_map(|(start, end)| {
+                        [
+                            (start, end.min(earlier.src_start)),
+                            (start.max(earlier.src_end()), end),
+                        ]
+                    })
+                    .filter(|(start, end)| start < end)
+                    .collect();
+            }
+
+            for (start, end) in uncovered {
+                result.push(MapRange {
+                    dst_start: range.dst_start + (start - range.src_start),
+                    src_start: start,
+                    len: end - start,
+                });
+            }
+        }
+
+        result.sort_by_key(|range| range.src_start);
+
+        Map(result)
+    }
+
+    /// Splits `range` into consecutive pieces that each move as one block, and
+    /// pairs every piece with the range it ends up in.
+    pub fn pieces(&self, range: SeedRange) -> Vec<(SeedRange, SeedRange)> {
+        let mut pieces = Vec::new();
+
+        if range.len == 0 {
+            return pieces;
+        }
+
+        let mut start = range.start;
+        let last = range.last();
+
+        for map in self.0.iter() {
+            if map.src_end() <= start {
+                continue;
+            }
+
+            if map.src_start > last {
+                break;
+            }
+
+            if map.src_start > start {
+                let gap = SeedRange {
+                    start,
+                    len: map.src_start - start,
+                };
+
+                pieces.push((gap, gap));
+                start = map.src_start;
+            }
+
+            let piece_last = (map.src_end() - 1).min(last);
+
+            pieces.push((
+                SeedRange {
+                    start,
+                    len: piece_last - start + 1,
+                },
+                SeedRange {
+                    start: map.dst_start + (start - map.src_start),
+                    len: piece_last - start + 1,
+                },
+            ));
+
+            if piece_last == last {
+                return pieces;
+            }
+
+            start = piece_last + 1;
+        }
+
+        let gap = SeedRange {
+            start,
+            len: last - start + 1,
+        };
+
+        pieces.push((gap, gap));
+        pieces
+    }
+
+    /// Returns the map that applies `self` first and `next` on the result.
+    pub fn then(&self, next: &Map) -> Map {
+        let everything = SeedRange {
+            start: 0,
+            len: u64::MAX,
+        };
+
+        let mut result = Vec::<MapRange>::new();
+
+        for (src, mid) in self.pieces(everything) {
+            for (next_src, dst) in next.pieces(mid) {
+                let range = MapRange {
+                    dst_start: dst.start,
+                    src_start: src.start + (next_src.start - mid.start),
+                    len: dst.len,
+                };
+
+                if range.dst_start == range.src_start {
+                    continue;
+                }
+
+                match result.last_mut() {
+                    Some(last)
+                        if last.src_end() == range.src_start
+                            && last.dst_start + last.len == range.dst_start =>
+                    {
+                        last.len += range.len;
+                    }
+                    _ => result.push(range),
+                }
+            }
+        }
+
+        Map(result)
+    }
+
+    /// Returns every source range that this map sends into `range`, sorted and merged.
+    pub fn preimage(&self, range: SeedRange) -> Vec<SeedRange> {
+        let mut result = Vec::new();
+
+        if range.len == 0 {
+            return result;
+        }
+
+        for map in self.0.iter() {
+            let dst = SeedRange {
+                start: map.dst_start,
+                len: map.len,
+            };
+
+            if let Some(hit) = dst.intersection(&range) {
+                result.push(SeedRange {
+                    start: map.src_start + (hit.start - map.dst_start),
+                    len: hit.len,
+                });
+            }
+        }
+
+        // Numbers that no range covers stay where they are
+        let mut start = range.start;
+
+        for map in self.0.iter() {
+            if let Some(covered) = range.intersection(&SeedRange {
+                start: map.src_start,
+                len: map.len,
+            }) {
+                if covered.start > start {
+                    result.push(SeedRange {
+                        start,
+                        len: covered.start - start,
+                    });
+                }
+
+                // A map range ends at most at u64::MAX, so this can't overflow
+                start = start.max(covered.last() + 1);
+            }
+        }
+
+        if start <= range.last() {
+            result.push(SeedRange {
+                start,
+                len: range.last() - start + 1,
+            });
+        }
+
+        merge_ranges(result)
+    }
+
+    pub fn map_number(&self, number: u64) -> u64 {
+        for range in self.0.iter() {
+            if range.src_start <= number && number < range.src_start + range.len {
+                return range.dst_start + (number - range.src_start);
+            }
+        }
+
+        number
+    }
+
+    #[cfg(test)]
+    fn map_ranges(&self, ranges: Vec<SeedRange>) -> Vec<SeedRange> {
+        let mut queue = ranges;
+        let mut result = Vec::new();
+
+        'outer: while let Some(range) = queue.pop() {
+            for map in self.0.iter() {
+                if let Some(intersection) = map.source_intersection(&range) {
+                    if intersection.start > range.start {
+                        queue.push(SeedRange {
+                            start: range.start,
+                            len: intersection.start - range.start,
+                        });
+                    }
+
+                    let intersection_end = intersection.start + intersection.len;
+                    let range_end = range.start + range.len;
+
+                    if intersection_end < range_end {
+                        queue.push(SeedRange {
+                            start: intersection_end,
+                            len: range_end - intersection_end,
+                        });
+                    }
+
+                    result.push(SeedRange {
+                        start: map.dst_start + (intersection.start - map.src_start),
+                        len: intersection.len,
+                    });
+
+                    continue 'outer;
+                }
+            }
+
+            result.push(range);
+        }
+
+        result
+    }
+}
+
+impl FromStr for Map {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let ranges = s
+            .lines()
+            .map(|line| line.parse::<MapRange>().map_err(|e| e.within(s, line)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Map::from_ranges(ranges))
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, range) in self.0.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", range)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn map_seed_to_location(&self, seed: u64) -> u64 {
+        let mut result = seed;
+
+        for map in self.maps.iter() {
+            result = map.map_number(result);
+        }
+
+        result
+    }
+
+    #[cfg(test)]
+    fn map_seed_ranges_to_location(&self, seed_ranges: Vec<SeedRange>) -> Vec<SeedRange> {
+        let mut result = seed_ranges;
+
+        for map in self.maps.iter() {
+            result = map.map_ranges(result);
+        }
+
+        result
+    }
+
+    /// Returns every seed range that ends up somewhere in `locations`, sorted and merged.
+    pub fn seeds_for_locations(&self, locations: SeedRange) -> Vec<SeedRange> {
+        let mut result = vec![locations];
+
+        for map in self.maps.iter().rev() {
+            result = merge_ranges(
+                result
+                    .into_iter()
+                    .flat_map(|range| map.preimage(range))
+                    .collect(),
+            );
+        }
+
+        result
+    }
+
+    pub fn seeds_for_location(&self, location: u64) -> Vec<SeedRange> {
+        self.seeds_for_locations(SeedRange {
+            start: location,
+            len: 1,
+        })
+    }
+
+    /// Finds the lowest location reachable from `seed_ranges` by walking the
+    /// locations upwards and mapping each block of them back to its seeds.
+    pub fn lowest_location_backwards(&self, seed_ranges: &[SeedRange]) -> Option<u64> {
+        let seed_to_location = self.seed_to_location();
+
+        // Between two consecutive boundaries, every location comes from the same pieces
+        let mut boundaries = vec![0, u64::MAX];
+
+        for range in seed_to_location.0.iter() {
+            boundaries.extend([
+                range.src_start,
+                range.src_end(),
+                range.dst_start,
+                range.dst_start + range.len,
+            ]);
+        }
+
+        boundaries.sort();
+        boundaries.dedup();
+
+        boundaries.windows(2).find_map(|bounds| {
+            let locations = SeedRange {
+                start: bounds[0],
+                len: bounds[1] - bounds[0],
+            };
+
+            seed_to_location
+                .preimage(locations)
+                .iter()
+                .flat_map(|seeds| seed_ranges.iter().filter_map(|r| r.intersection(seeds)))
+                // Neighbouring pieces of seeds can land anywhere in the block, so
+                // the lowest location isn't always where the first seed goes
+                .flat_map(|seeds| seed_to_location.pieces(seeds))
+                .map(|(_, location)| location.start)
+                .min()
+        })
+    }
+
+    pub fn seed_ranges(&self) -> Result<Vec<SeedRange>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(Error::Invalid(
+                "seeds must come in start and length pairs".to_string(),
+            ));
+        }
+
+        self.seeds
+            .chunks_exact(2)
+            .map(|start_len| match start_len[0].checked_add(start_len[1]) {
+                Some(_) => Ok(SeedRange {
+                    start: start_len[0],
+                    len: start_len[1],
+                }),
+                None => Err(Error::Invalid(format!(
+                    "the seed range starting at {} runs past the largest number",
+                    start_len[0]
+                ))),
+            })
+            .collect()
+    }
+
+    /// Collapses every map in the almanac into a single seed to location map.
+    pub fn seed_to_location(&self) -> Map {
+        self.maps
+            .iter()
+            .fold(Map::from_ranges([]), |composed, map| composed.then(map))
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s.lines();
+
+        let seeds = lines.next().unwrap_or(s);
+        let seeds = seeds
+            .strip_prefix("seeds: ")
+            .ok_or_else(|| Error::at(s, seeds, "expected \"seeds: \""))?
+            .split_whitespace()
+            .map(|n| error::number::<u64>(s, n))
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some(line) = lines.next().filter(|line| !line.is_empty()) {
+            return Err(Error::at(s, line, "expected an empty line after the seeds"));
+        }
+
+        let mut maps = Vec::new();
+
+        while let Some(header) = lines.next() {
+            if !header.ends_with(" map:") {
+                return Err(Error::at(s, header, "expected a map header"));
+            }
+
+            let mut ranges = Vec::new();
+
+            for line in lines.by_ref() {
+                if line.is_empty() {
+                    break;
+                }
+
+                ranges.push(MapRange::from_str(line).map_err(|e| e.within(s, line))?);
+            }
+
+            maps.push(Map::from_ranges(ranges));
+        }
+
+        Ok(Almanac { seeds, maps })
+    }
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day5))]
+pub fn generator(input: &str) -> Result<Almanac> {
+    input.parse()
+}
+
+#[cfg_attr(feature = "runner", aoc(day5, part1))]
+pub fn part1(almanac: &Almanac) -> Result<u64> {
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.map_seed_to_location(*seed))
+        .min()
+        .ok_or_else(|| Error::NoSolution("the almanac lists no seeds".to_string()))
+}
+
+#[cfg_attr(feature = "runner", aoc(day5, part2))]
+pub fn part2(almanac: &Almanac) -> Result<u64> {
+    let seed_ranges = almanac.seed_ranges()?;
+    let seed_to_location = almanac.seed_to_location();
+
+    seed_ranges
+        .into_iter()
+        .flat_map(|range| seed_to_location.pieces(range))
+        .map(|(_, location)| location.start)
+        .min()
+        .ok_or_else(|| Error::NoSolution("the almanac lists no seeds".to_string()))
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&generator("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4").unwrap()), Ok(35));
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4").unwrap()), Ok(46));
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        generator("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50\n").err(),
+        Some(Error::parse(5, 1, "expected \"<dst> <src> <len>\""))
+    );
+
+    assert_eq!(
+        generator("seeds: 79 14\n\nseed-to-soil map:\n18446744073709551615 0 2\n").err(),
+        Some(Error::parse(4, 1, "the range runs past the largest number"))
+    );
+
+    assert_eq!(
+        part2(&generator("seeds: 18446744073709551615 1\n").unwrap()),
+        Err(Error::Invalid(
+            "the seed range starting at 18446744073709551615 runs past the largest number"
+                .to_string()
+        ))
+    );
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15\n\nfertilizer-to-water map:\n49 53 8\n0 11 42\n42 0 7\n57 7 4\n\nwater-to-light map:\n88 18 7\n18 25 70\n\nlight-to-temperature map:\n45 77 23\n81 45 19\n68 64 13\n\ntemperature-to-humidity map:\n0 69 1\n1 0 69\n\nhumidity-to-location map:\n60 56 37\n56 93 4";
+
+#[test]
+fn test_composed_map() {
+    let almanac = generator(EXAMPLE).unwrap();
+    let composed = almanac.seed_to_location();
+
+    for seed in 0..200 {
+        assert_eq!(
+            composed.map_number(seed),
+            almanac.map_seed_to_location(seed)
+        );
+    }
+
+    let seed_ranges = vec![
+        SeedRange { start: 79, len: 14 },
+        SeedRange { start: 55, len: 13 },
+    ];
+
+    let mut layered = almanac.map_seed_ranges_to_location(seed_ranges.clone());
+    let mut single_pass = seed_ranges
+        .into_iter()
+        .flat_map(|range| composed.pieces(range))
+        .map(|(_, location)| location)
+        .collect::<Vec<_>>();
+
+    layered.sort();
+    single_pass.sort();
+
+    let flatten = |ranges: Vec<SeedRange>| {
+        ranges
+            .into_iter()
+            .flat_map(|r| r.start..r.start + r.len)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(flatten(layered), flatten(single_pass));
+}
+
+#[test]
+fn test_map_round_trip() {
+    let map = "50 98 2\n52 50 48\n0 49 3".parse::<Map>().unwrap();
+
+    assert_eq!(map.to_string(), "0 49 1\n52 50 48\n50 98 2");
+    assert_eq!(map.to_string().parse::<Map>().unwrap(), map);
+
+    let twice = map.then(&map);
+
+    for n in 0..120 {
+        assert_eq!(twice.map_number(n), map.map_number(map.map_number(n)));
+    }
+}
+
+#[test]
+fn test_inverse_map() {
+    let map = "10 0 5\n10 20 5\n30 10 2".parse::<Map>().unwrap();
+
+    // 10..12 is reached from both moved ranges, while 10 and 11 themselves move away
+    assert_eq!(
+        map.preimage(SeedRange { start: 10, len: 3 }),
+        [
+            SeedRange { start: 0, len: 3 },
+            SeedRange { start: 12, len: 1 },
+            SeedRange { start: 20, len: 3 },
+        ]
+    );
+
+    assert_eq!(
+        map.preimage(SeedRange { start: 30, len: 1 }),
+        [
+            SeedRange { start: 10, len: 1 },
+            SeedRange { start: 30, len: 1 }
+        ]
+    );
+}
+
+#[test]
+fn test_seeds_for_locations() {
+    let almanac = generator(EXAMPLE).unwrap();
+
+    let locations = SeedRange { start: 40, len: 20 };
+    let seeds = almanac.seeds_for_locations(locations);
+
+    let expected = (0..1000)
+        .filter(|&seed| {
+            locations
+                .intersection(&SeedRange {
+                    start: almanac.map_seed_to_location(seed),
+                    len: 1,
+                })
+                .is_some()
+        })
+        .collect::<Vec<_>>();
+
+    let found = seeds
+        .iter()
+        .flat_map(|r| r.start..=r.last())
+        .collect::<Vec<_>>();
+
+    assert_eq!(found, expected);
+    assert_eq!(almanac.seed_to_location().preimage(locations), seeds);
+
+    assert_eq!(
+        almanac.seeds_for_location(35),
+        [SeedRange { start: 13, len: 1 }]
+    );
+}
+
+#[test]
+fn test_lowest_location_backwards() {
+    let almanac = generator(EXAMPLE).unwrap();
+    let seed_ranges = almanac.seed_ranges().unwrap();
+
+    assert_eq!(almanac.lowest_location_backwards(&seed_ranges), Some(46));
+    assert_eq!(
+        almanac.lowest_location_backwards(&[SeedRange { start: 79, len: 1 }]),
+        Some(82)
+    );
+
+    // Seeds 9 and 10 go to 109 and 105, which are in the same block of locations
+    let almanac = generator("seeds: 9 2\n\nseed-to-location map:\n100 0 10\n105 10 10").unwrap();
+
+    assert_eq!(
+        almanac.lowest_location_backwards(&almanac.seed_ranges().unwrap()),
+        Some(105)
+    );
+    assert_eq!(part2(&almanac), Ok(105));
+}
+
+#[test]
+fn test_largest_location() {
+    let almanac = generator(EXAMPLE).unwrap();
+
+    // No map range reaches u64::MAX, so only that seed ends up there
+    assert_eq!(
+        almanac.seeds_for_location(u64::MAX),
+        [SeedRange {
+            start: u64::MAX,
+            len: 1
+        }]
+    );
+    assert_eq!(
+        almanac.seeds_for_locations(SeedRange {
+            start: u64::MAX - 1,
+            len: 2
+        }),
+        [SeedRange {
+            start: u64::MAX - 1,
+            len: 2
+        }]
+    );
+
+    let map = "0 10 5".parse::<Map>().unwrap();
+    let pieces = map.pieces(SeedRange {
+        start: 12,
+        len: u64::MAX - 12,
+    });
+
+    assert_eq!(pieces.len(), 2);
+    assert_eq!(pieces[1].0.last(), u64::MAX - 1);
+}
+
+/// Small almanacs whose ranges may overlap, so the first one listed has to win.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, strategy::Strategy};
+
+    let seeds = vec((0u64..100, 1u64..20), 1..4);
+    let range = (0u64..120, 0u64..120, 1u64..30);
+    let maps = vec(vec(range, 0..5), 1..5);
+
+    (seeds, maps).prop_map(|(seeds, maps)| {
+        let seeds = seeds
+            .iter()
+            .map(|(start, len)| format!("{} {}", start, len))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let maps = maps
+            .iter()
+            .enumerate()
+            .map(|(idx, ranges)| {
+                let ranges = ranges
+                    .iter()
+                    .map(|(dst, src, len)| format!("\n{} {} {}", dst, src, len));
+
+                format!(
+                    "map{}-to-map{} map:{}",
+                    idx,
+                    idx + 1,
+                    ranges.collect::<String>()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        format!("seeds: {}\n\n{}", seeds, maps)
+    })
+}
+
+/// Reads the seeds and every map's `(dst, src, len)` lines straight from the text.
+#[cfg(test)]
+fn parse_brute_force(input: &str) -> (Vec<u64>, Vec<Vec<Vec<u64>>>) {
+    let numbers = |line: &str| {
+        line.split_whitespace()
+            .filter_map(|n| n.parse().ok())
+            .collect::<Vec<u64>>()
+    };
+
+    let mut sections = input.split("\n\n");
+    let seeds = numbers(sections.next().unwrap());
+    let maps = sections
+        .map(|section| section.lines().skip(1).map(numbers).collect())
+        .collect();
+
+    (seeds, maps)
+}
+
+/// Sends one seed through every map, using the first listed range that covers it.
+#[cfg(test)]
+fn location_brute_force(maps: &[Vec<Vec<u64>>], seed: u64) -> u64 {
+    maps.iter().fold(seed, |n, ranges| {
+        ranges
+            .iter()
+            .find(|r| r[1] <= n && n < r[1] + r[2])
+            .map_or(n, |r| r[0] + (n - r[1]))
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let almanac = generator(&input).unwrap();
+        let (seeds, maps) = parse_brute_force(&input);
+
+        let lowest = |seeds: Vec<u64>| {
+            seeds
+                .into_iter()
+                .map(|seed| location_brute_force(&maps, seed))
+                .min()
+        };
+
+        let every_seed = seeds
+            .chunks(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .collect();
+
+        proptest::prop_assert_eq!(part1(&almanac).ok(), lowest(seeds.clone()));
+        proptest::prop_assert_eq!(part2(&almanac).ok(), lowest(every_seed));
+    }
+
+    #[test]
+    fn test_map_ranges_matches_brute_force(input in arbitrary_input()) {
+        let almanac = generator(&input).unwrap();
+        let (_, maps) = parse_brute_force(&input);
+        let seed_ranges = almanac.seed_ranges().unwrap();
+
+        let mut layered = almanac
+            .map_seed_ranges_to_location(seed_ranges.clone())
+            .iter()
+            .flat_map(|r| r.start..=r.last())
+            .collect::<Vec<_>>();
+
+        let mut expected = seed_ranges
+            .iter()
+            .flat_map(|r| r.start..=r.last())
+            .map(|seed| location_brute_force(&maps, seed))
+            .collect::<Vec<_>>();
+
+        layered.sort();
+        expected.sort();
+
+        proptest::prop_assert_eq!(layered, expected);
+    }
+
+    #[test]
+    fn test_pieces_match_brute_force(input in arbitrary_input()) {
+        let almanac = generator(&input).unwrap();
+        let (_, maps) = parse_brute_force(&input);
+        let seed_to_location = almanac.seed_to_location();
+
+        for range in almanac.seed_ranges().unwrap() {
+            let pieces = seed_to_location.pieces(range);
+
+            // The pieces cover the range in order, and each one moves as a block
+            proptest::prop_assert_eq!(pieces.first().map(|(src, _)| src.start), Some(range.start));
+            proptest::prop_assert_eq!(pieces.last().map(|(src, _)| src.last()), Some(range.last()));
+
+            for (src, dst) in pieces {
+                for offset in 0..src.len {
+                    proptest::prop_assert_eq!(
+                        location_brute_force(&maps, src.start + offset),
+                        dst.start + offset
+                    );
+                }
+            }
+        }
+    }
+}
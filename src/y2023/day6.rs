@@ -0,0 +1,293 @@
+use crate::{math, parse::Cursor, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+/// Splits the input into the numbers following "Time:" and "Distance:".
+fn parse_lines(input: &str) -> Result<(Cursor<'_>, Cursor<'_>)> {
+    let mut distances = Cursor::new(input.as_bytes());
+    let times = distances
+        .until(b'\n')
+        .ok_or_else(|| distances.error("expected two lines"))?;
+
+    let mut times = distances.at(times);
+    times.expect("Time:")?;
+    distances.expect("Distance:")?;
+
+    let rest = distances.rest();
+    let end = rest.iter().rposition(|&b| b != b'\n').map_or(0, |i| i + 1);
+
+    Ok((times, distances.at(&rest[..end])))
+}
+
+/// Reads a number that was printed with spaces between its digits.
+fn kerned_number(s: Cursor) -> Result<u64> {
+    let digits = s.rest();
+    let error = || s.error_at(digits, "expected a number");
+
+    let mut number = None::<u64>;
+
+    for &b in digits.iter().filter(|&&b| b != b' ') {
+        if !b.is_ascii_digit() {
+            return Err(error());
+        }
+
+        number = number
+            .unwrap_or(0)
+            .checked_mul(10)
+            .and_then(|n| n.checked_add((b - b'0') as u64));
+
+        if number.is_none() {
+            return Err(error());
+        }
+    }
+
+    number.ok_or_else(error)
+}
+
+impl Race {
+    /// Counts the ways to hold the button so that the boat beats the record.
+    ///
+    /// Holding for `h` milliseconds travels `h * (time - h)`, so the winning
+    /// holds lie strictly between the roots of `h^2 - time * h + distance`.
+    /// Everything is worked out exactly in `u128`, where the square of any
+    /// `u64` fits.
+    pub fn ways_to_win(&self) -> u64 {
+        let time = self.time as u128;
+        let distance = self.distance as u128;
+
+        let wins = |hold: u128| hold * (time - hold) > distance;
+
+        let discriminant = match (time * time).checked_sub(4 * distance) {
+            Some(discriminant) if discriminant > 0 => discriminant,
+            _ => return 0,
+        };
+
+        // The lower root is (time - sqrt(discriminant)) / 2, and flooring the
+        // square root overshoots it by less than one half, so starting one below
+        // that guess leaves at most two steps to the first winning hold.
+        let root = math::isqrt(discriminant).expect("an unsigned number has a square root");
+        let mut first = ((time - root) / 2).saturating_sub(1);
+
+        while !wins(first) {
+            if first >= time / 2 {
+                return 0;
+            }
+
+            first += 1;
+        }
+
+        // The distance is symmetric around time / 2
+        (time - 2 * first + 1) as u64
+    }
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day6, part1))]
+pub fn generator_part1(input: &str) -> Result<Vec<Race>> {
+    let (times, distances) = parse_lines(input)?;
+    let mut times = times.numbers();
+    let mut distances = distances.numbers();
+    let mut races = Vec::new();
+
+    loop {
+        match (times.next(), distances.next()) {
+            (Some(time), Some(distance)) => races.push(Race {
+                time: time?,
+                distance: distance?,
+            }),
+            (None, None) => return Ok(races),
+            _ => {
+                return Err(Error::Invalid(
+                    "there must be as many distances as times".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Reads the sheet as one race, ignoring the spaces between the digits.
+#[cfg_attr(feature = "runner", aoc_generator(day6, part2))]
+pub fn generator_part2(input: &str) -> Result<Race> {
+    let (time, distance) = parse_lines(input)?;
+
+    Ok(Race {
+        time: kerned_number(time)?,
+        distance: kerned_number(distance)?,
+    })
+}
+
+#[cfg_attr(feature = "runner", aoc(day6, part1))]
+pub fn part1(input: &[Race]) -> Result<u64> {
+    input.iter().try_fold(1u64, |product, race| {
+        product
+            .checked_mul(race.ways_to_win())
+            .ok_or_else(|| Error::Invalid("the product doesn't fit in a u64".to_string()))
+    })
+}
+
+#[cfg_attr(feature = "runner", aoc(day6, part2))]
+pub fn part2(input: &Race) -> u64 {
+    input.ways_to_win()
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        part1(&generator_part1("Time:      7  15   30\nDistance:  9  40  200").unwrap()),
+        Ok(288)
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        part2(&generator_part2("Time:      7  15   30\nDistance:  9  40  200").unwrap()),
+        71503
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        generator_part1("Time: 7 15").err(),
+        Some(Error::parse(1, 1, "expected two lines"))
+    );
+    assert_eq!(
+        generator_part1("Time: 7 15\nDistance: 9").err(),
+        Some(Error::Invalid(
+            "there must be as many distances as times".to_string()
+        ))
+    );
+    assert_eq!(
+        generator_part2("Time: 7 1x\nDistance: 9 40").err(),
+        Some(Error::parse(1, 6, "expected a number"))
+    );
+    assert_eq!(
+        generator_part2("Time: 7\nSpeed: 9").err(),
+        Some(Error::parse(2, 1, "expected \"Distance:\""))
+    );
+    assert_eq!(
+        generator_part2("Time: 99999999999999999999\nDistance: 9").err(),
+        Some(Error::parse(1, 6, "expected a number"))
+    );
+}
+
+#[test]
+fn test_product_overflow() {
+    let races = generator_part1("Time: 18446744073709551615 5\nDistance: 0 0").unwrap();
+
+    assert_eq!(
+        part1(&races),
+        Err(Error::Invalid(
+            "the product doesn't fit in a u64".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_no_allocations() {
+    let input = crate::gen::generate(2023, 6, 1, &[]);
+    let races = generator_part1(input.trim_end()).unwrap();
+    let race = generator_part2(input.trim_end()).unwrap();
+    let (_, allocations) = crate::memory::measure(|| (part1(&races), part2(&race)));
+
+    assert_eq!(allocations.map(|a| a.allocations), Some(0));
+}
+
+#[cfg(test)]
+fn ways_to_win_brute_force(race: &Race) -> u64 {
+    (0..=race.time)
+        .filter(|&hold| hold * (race.time - hold) > race.distance)
+        .count() as u64
+}
+
+#[test]
+fn test_ways_to_win() {
+    // A perfect square discriminant means both roots only tie the record
+    assert_eq!(
+        Race {
+            time: 30,
+            distance: 200
+        }
+        .ways_to_win(),
+        9
+    );
+
+    assert_eq!(
+        Race {
+            time: 4,
+            distance: 4
+        }
+        .ways_to_win(),
+        0
+    );
+
+    assert_eq!(
+        Race {
+            time: 3,
+            distance: 2
+        }
+        .ways_to_win(),
+        0
+    );
+
+    assert_eq!(
+        Race {
+            time: u64::MAX,
+            distance: 0
+        }
+        .ways_to_win(),
+        u64::MAX - 1
+    );
+}
+
+/// A sheet of one or two races, short enough that the kerned race can be brute forced.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, strategy::Strategy};
+
+    vec((0u64..100, 0u64..2_500), 1..3).prop_map(|races| {
+        let (times, distances): (Vec<_>, Vec<_>) = races
+            .iter()
+            .map(|(time, distance)| (format!("{:>5}", time), format!("{:>5}", distance)))
+            .unzip();
+
+        format!("Time:{}\nDistance:{}", times.concat(), distances.concat())
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_ways_to_win_matches_brute_force(time in 0u64..2_000, distance in 0u64..1_100_000) {
+        let race = Race { time, distance };
+
+        proptest::prop_assert_eq!(race.ways_to_win(), ways_to_win_brute_force(&race));
+    }
+
+    #[test]
+    fn test_ways_to_win_near_the_record(time in 0u64..2_000, offset in 0u64..3) {
+        // Records sitting right at or next to the best possible distance
+        let best = (time / 2) * (time - time / 2);
+        let race = Race { time, distance: best.saturating_sub(offset) };
+
+        proptest::prop_assert_eq!(race.ways_to_win(), ways_to_win_brute_force(&race));
+    }
+
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let (times, distances) = parse_lines(&input).unwrap();
+        let races = times
+            .numbers()
+            .zip(distances.numbers())
+            .map(|(time, distance)| Race { time: time.unwrap(), distance: distance.unwrap() })
+            .collect::<Vec<_>>();
+        let kerned = Race { time: kerned_number(times).unwrap(), distance: kerned_number(distances).unwrap() };
+
+        proptest::prop_assert_eq!(part1(&generator_part1(&input).unwrap()), Ok(races.iter().map(ways_to_win_brute_force).product::<u64>()));
+        proptest::prop_assert_eq!(part2(&generator_part2(&input).unwrap()), ways_to_win_brute_force(&kerned));
+    }
+}
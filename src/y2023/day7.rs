@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use crate::{error, Error, Result};
+
+trait Sorted {
+    fn sorted(self) -> Self;
+}
+
+impl<T: Sized> Sorted for Vec<T>
+where
+    T: Ord,
+{
+    fn sorted(mut self) -> Self {
+        self.sort();
+        self
+    }
+}
+
+/// Every card label that can appear in a hand.
+const DECK: &str = "AKQJT98765432";
+
+/// How many cards a hand holds of each rank, largest group first.
+///
+/// Comparing these lexicographically ranks the usual hands correctly: five of a
+/// kind is `[5]`, four of a kind `[4, 1]`, a full house `[3, 2]`, three of a kind
+/// `[3, 1, 1]`, and so on down to high card.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandKind(Vec<usize>);
+
+/// How to order two hands of the same kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the cards one by one in the order they were dealt.
+    FirstCard,
+    /// Compare the strongest card of each hand, then the next strongest, and so on.
+    HighestCard,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    /// Card labels from strongest to weakest.
+    pub order: String,
+    /// Labels that stand in for whichever rank makes the best hand.
+    pub wildcards: String,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+
+impl Ruleset {
+    pub fn standard() -> Self {
+        Ruleset {
+            order: DECK.to_string(),
+            wildcards: String::new(),
+            hand_size: 5,
+            tie_break: TieBreak::FirstCard,
+        }
+    }
+
+    /// Jacks become jokers, which are wild but the weakest card on their own.
+    pub fn jokers() -> Self {
+        Ruleset {
+            order: "AKQT98765432J".to_string(),
+            wildcards: "J".to_string(),
+            ..Ruleset::standard()
+        }
+    }
+
+    /// Reads a hand, which may only hold cards from this ruleset's order.
+    pub fn parse_hand(&self, s: &str) -> Result<Hand> {
+        if s.is_empty() {
+            return Err(Error::at(s, s, "expected a hand"));
+        }
+
+        if let Some((i, c)) = s.char_indices().find(|&(_, c)| !self.order.contains(c)) {
+            return Err(Error::at(s, &s[i..], format!("invalid card {:?}", c)));
+        }
+
+        Ok(Hand(s.chars().collect()))
+    }
+
+    /// Reads one hand and its bet per line, checking the cards against this ruleset.
+    pub fn hands(&self, input: &str) -> Result<Vec<(Hand, usize)>> {
+        input
+            .lines()
+            .map(|line| {
+                let (hand, bet) = line
+                    .split_once(' ')
+                    .ok_or_else(|| Error::at(input, line, "expected \"<hand> <bet>\""))?;
+
+                let hand = self.parse_hand(hand).map_err(|e| e.within(input, hand))?;
+                let bet = error::number::<usize>(input, bet)?;
+
+                Ok((hand, bet))
+            })
+            .collect()
+    }
+
+    fn strength(&self, card: char) -> Result<usize> {
+        self.order
+            .chars()
+            .rev()
+            .position(|c| c == card)
+            .ok_or_else(|| Error::Invalid(format!("card {:?} isn't part of the ruleset", card)))
+    }
+
+    pub fn kind(&self, hand: &Hand) -> Result<HandKind> {
+        if hand.0.len() != self.hand_size {
+            return Err(Error::Invalid(format!(
+                "hand {} has {} cards, expected {}",
+                hand,
+                hand.0.len(),
+                self.hand_size
+            )));
+        }
+
+        let mut counts = HashMap::new();
+        let mut wildcards = 0;
+
+        for &card in &hand.0 {
+            if self.wildcards.contains(card) {
+                wildcards += 1;
+            } else {
+                *counts.entry(card).or_insert(0usize) += 1;
+            }
+        }
+
+        let mut groups = counts.into_values().collect::<Vec<_>>().sorted();
+        groups.reverse();
+
+        // Wildcards always do best by joining the largest group
+        match groups.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None => groups.push(wildcards),
+        }
+
+        Ok(HandKind(groups))
+    }
+
+    /// Orders hands from weakest to strongest and adds up each bet times its rank.
+    pub fn winnings(&self, hands: &[(Hand, usize)]) -> Result<usize> {
+        let mut ranked = Vec::with_capacity(hands.len());
+
+        for (hand, bet) in hands {
+            let mut strengths = hand
+                .0
+                .iter()
+                .map(|&card| self.strength(card))
+                .collect::<Result<Vec<_>>>()?;
+
+            if self.tie_break == TieBreak::HighestCard {
+                strengths.sort_by(|a, b| b.cmp(a));
+            }
+
+            ranked.push((self.kind(hand)?, strengths, *bet));
+        }
+
+        let overflow = || Error::Invalid("the winnings don't fit in a usize".to_string());
+
+        ranked
+            .sorted()
+            .iter()
+            .enumerate()
+            .try_fold(0usize, |total, (rank, (_, _, bet))| {
+                (rank + 1)
+                    .checked_mul(*bet)
+                    .and_then(|winnings| total.checked_add(winnings))
+                    .ok_or_else(overflow)
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand(Vec<char>);
+
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+/// Both parts play with the same cards, so the standard ruleset reads the hands for either.
+#[cfg_attr(feature = "runner", aoc_generator(day7))]
+pub fn generator(input: &str) -> Result<Vec<(Hand, usize)>> {
+    Ruleset::standard().hands(input)
+}
+
+#[cfg_attr(feature = "runner", aoc(day7, part1))]
+pub fn part1(input: &[(Hand, usize)]) -> Result<usize> {
+    Ruleset::standard().winnings(input)
+}
+
+#[cfg_attr(feature = "runner", aoc(day7, part2))]
+pub fn part2(input: &[(Hand, usize)]) -> Result<usize> {
+    Ruleset::jokers().winnings(input)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        part1(&generator("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap()),
+        Ok(6440)
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        part2(&generator("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap()),
+        Ok(5905)
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        generator("32T3K 765\nT55X5 684").err(),
+        Some(Error::parse(2, 4, "invalid card 'X'"))
+    );
+
+    assert_eq!(
+        part1(&generator("32T3K 765\nT55J 684").unwrap()),
+        Err(Error::Invalid(
+            "hand T55J has 4 cards, expected 5".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_winnings_overflow() {
+    let input = format!("23456 {}\nAAAAA {}", usize::MAX / 2, usize::MAX / 2);
+
+    assert_eq!(
+        part1(&generator(&input).unwrap()),
+        Err(Error::Invalid(
+            "the winnings don't fit in a usize".to_string()
+        ))
+    );
+}
+
+#[cfg(test)]
+fn kind(ruleset: &Ruleset, hand: &str) -> Vec<usize> {
+    ruleset.kind(&ruleset.parse_hand(hand).unwrap()).unwrap().0
+}
+
+#[test]
+fn test_hand_kinds() {
+    let standard = Ruleset::standard();
+    let jokers = Ruleset::jokers();
+
+    assert_eq!(kind(&standard, "AAAAA"), [5]);
+    assert_eq!(kind(&standard, "AA8AA"), [4, 1]);
+    assert_eq!(kind(&standard, "23332"), [3, 2]);
+    assert_eq!(kind(&standard, "TTT98"), [3, 1, 1]);
+    assert_eq!(kind(&standard, "23432"), [2, 2, 1]);
+    assert_eq!(kind(&standard, "A23A4"), [2, 1, 1, 1]);
+    assert_eq!(kind(&standard, "23456"), [1, 1, 1, 1, 1]);
+
+    assert_eq!(kind(&jokers, "JJJJJ"), [5]);
+    assert_eq!(kind(&jokers, "KTJJT"), [4, 1]);
+    assert_eq!(kind(&jokers, "2233J"), [3, 2]);
+    assert_eq!(kind(&jokers, "2345J"), [2, 1, 1, 1]);
+}
+
+#[test]
+fn test_house_rules() {
+    let twos_wild = Ruleset {
+        wildcards: "2".to_string(),
+        ..Ruleset::standard()
+    };
+
+    assert_eq!(kind(&twos_wild, "2K2QA"), [3, 1, 1]);
+    assert_eq!(
+        twos_wild.winnings(&generator("2K2QA 10\nKKQQA 1").unwrap()),
+        Ok(10 * 2 + 1)
+    );
+
+    let seven_cards = Ruleset {
+        hand_size: 7,
+        ..Ruleset::jokers()
+    };
+
+    assert_eq!(kind(&seven_cards, "KKKQQJ2"), [4, 2, 1]);
+    assert_eq!(kind(&seven_cards, "KKKQQQ2"), [3, 3, 1]);
+    assert_eq!(
+        seven_cards.winnings(&generator("KKKQQQ2 3\nKKKQQJ2 5\nAKQT987 7").unwrap()),
+        Ok(7 + 3 * 2 + 5 * 3)
+    );
+
+    let highest_card = Ruleset {
+        tie_break: TieBreak::HighestCard,
+        ..Ruleset::standard()
+    };
+
+    // The ace wins, even though the other hand starts with the stronger card
+    assert_eq!(
+        highest_card.winnings(&generator("2345A 1\n6789K 2").unwrap()),
+        Ok(4)
+    );
+
+    let with_ones = Ruleset {
+        order: "AKQJT987654321".to_string(),
+        ..Ruleset::standard()
+    };
+
+    assert_eq!(
+        with_ones.winnings(&with_ones.hands("11112 3\n22221 5").unwrap()),
+        Ok(3 + 5 * 2)
+    );
+    assert_eq!(
+        generator("11112 3").err(),
+        Some(Error::parse(1, 1, "invalid card '1'"))
+    );
+}
+
+/// A few hands with their bets, often repeating cards so every kind shows up.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, sample::select, strategy::Strategy};
+
+    const CARDS: &[char] = &['A', 'K', 'J', 'T', '2', '2', 'J'];
+
+    let hand = vec(select(CARDS), 5).prop_map(|cards| cards.into_iter().collect::<String>());
+
+    vec((hand, 1usize..1000), 1..12).prop_map(|hands| {
+        hands
+            .iter()
+            .map(|(hand, bet)| format!("{} {}", hand, bet))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// Names the type of a hand from its card counts, trying each useful rank for every joker.
+#[cfg(test)]
+fn hand_type_brute_force(hand: &str, jokers: bool) -> usize {
+    if jokers {
+        if let Some(idx) = hand.find('J') {
+            // Ranks missing from the hand all behave the same, so one of them is enough
+            return hand
+                .chars()
+                .chain(DECK.chars().find(|&c| c != 'J' && !hand.contains(c)))
+                .filter(|&c| c != 'J')
+                .map(|c| {
+                    let replaced = format!("{}{}{}", &hand[..idx], c, &hand[idx + 1..]);
+                    hand_type_brute_force(&replaced, true)
+                })
+                .max()
+                .unwrap();
+        }
+    }
+
+    let mut counts = DECK
+        .chars()
+        .map(|c| hand.matches(c).count())
+        .filter(|&n| n > 0)
+        .collect::<Vec<_>>()
+        .sorted();
+    counts.reverse();
+
+    match counts[..] {
+        [5] => 6,
+        [4, 1] => 5,
+        [3, 2] => 4,
+        [3, 1, 1] => 3,
+        [2, 2, 1] => 2,
+        [2, 1, 1, 1] => 1,
+        _ => 0,
+    }
+}
+
+/// Ranks each hand by counting how many others it beats.
+#[cfg(test)]
+fn winnings_brute_force(input: &str, jokers: bool) -> usize {
+    let order = if jokers { "AKQT98765432J" } else { DECK };
+
+    let hands = input
+        .lines()
+        .map(|line| {
+            let (hand, bet) = line.split_once(' ').unwrap();
+            let cards = hand
+                .chars()
+                .map(|c| order.len() - order.find(c).unwrap())
+                .collect::<Vec<_>>();
+
+            (
+                (hand_type_brute_force(hand, jokers), cards),
+                bet.parse::<usize>().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, (strength, bet))| {
+            // Equal hands take their ranks in order of their bets, as a sort would
+            let beaten = hands
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| (&other.0, other.1, j) < (strength, *bet, i))
+                .count();
+
+            (beaten + 1) * bet
+        })
+        .sum()
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_wildcards_join_the_largest_group(hand in "[AKQJT98765432]{5}") {
+        let jokers = Ruleset::jokers();
+
+        // Try every rank for every joker and keep the best resulting hand
+        let mut candidates = vec![String::new()];
+
+        for card in hand.chars() {
+            let options = if card == 'J' { DECK.to_string() } else { card.to_string() };
+
+            candidates = candidates
+                .iter()
+                .flat_map(|prefix| options.chars().map(move |c| format!("{}{}", prefix, c)))
+                .collect();
+        }
+
+        let best = candidates
+            .iter()
+            .map(|candidate| kind(&Ruleset::standard(), candidate))
+            .max()
+            .unwrap();
+
+        proptest::prop_assert_eq!(kind(&jokers, &hand), best);
+    }
+
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let hands = generator(&input).unwrap();
+
+        proptest::prop_assert_eq!(part1(&hands), Ok(winnings_brute_force(&input, false)));
+        proptest::prop_assert_eq!(part2(&hands), Ok(winnings_brute_force(&input, true)));
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    collections::{
+        hash_map::Entry::{Occupied, Vacant},
+        HashMap,
+    },
+    fmt::{self, Debug},
+    str::FromStr,
+};
+
+use crate::{math::crt, Error, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct NodeId([u8; 3]);
+
+impl NodeId {
+    fn is_ghost_start(&self) -> bool {
+        self.0[2] == b'A'
+    }
+
+    fn is_ghost_end(&self) -> bool {
+        self.0[2] == b'Z'
+    }
+}
+
+impl Debug for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:03}", std::str::from_utf8(&self.0).unwrap())
+    }
+}
+
+impl FromStr for NodeId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if !s.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(Error::at(s, s, format!("invalid node id {:?}", s)));
+        }
+
+        s.as_bytes()
+            .try_into()
+            .map(NodeId)
+            .map_err(|_| Error::at(s, s, "a node id must be 3 characters long"))
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    left: NodeId,
+    right: NodeId,
+}
+
+#[derive(Debug)]
+pub struct Network {
+    instructions: String,
+    nodes: HashMap<NodeId, Node>,
+}
+
+impl Network {
+    fn node(&self, id: NodeId) -> Result<&Node> {
+        self.nodes
+            .get(&id)
+            .ok_or_else(|| Error::Invalid(format!("node {:?} is not in the network", id)))
+    }
+}
+
+#[cfg_attr(feature = "runner", aoc_generator(day8))]
+pub fn generator(input: &str) -> Result<Network> {
+    let (instructions, nodes) = input.split_once("\n\n").ok_or_else(|| {
+        Error::at(
+            input,
+            input,
+            "expected an empty line after the instructions",
+        )
+    })?;
+
+    if instructions.is_empty() {
+        return Err(Error::at(input, instructions, "expected instructions"));
+    }
+
+    if let Some(idx) = instructions.find(|c| c != 'L' && c != 'R') {
+        return Err(Error::at(
+            input,
+            &instructions[idx..],
+            "expected \"L\" or \"R\"",
+        ));
+    }
+
+    let mut network = Network {
+        instructions: instructions.to_string(),
+        nodes: HashMap::new(),
+    };
+
+    for line in nodes.lines() {
+        let (id, rest) = line
+            .split_once('=')
+            .ok_or_else(|| Error::at(input, line, "expected \"<id> = (<left>, <right>)\""))?;
+
+        let (left, right) = rest
+            .split_once(',')
+            .ok_or_else(|| Error::at(input, rest, "expected \"(<left>, <right>)\""))?;
+
+        let left = left
+            .trim()
+            .strip_prefix('(')
+            .ok_or_else(|| Error::at(input, left, "expected \"(\""))?;
+        let right = right
+            .trim()
+            .strip_suffix(')')
+            .ok_or_else(|| Error::at(input, right, "expected \")\""))?;
+
+        let parse = |s: &str| s.parse::<NodeId>().map_err(|e| e.within(input, s));
+
+        network.nodes.insert(
+            parse(id.trim())?,
+            Node {
+                left: parse(left)?,
+                right: parse(right)?,
+            },
+        );
+    }
+
+    Ok(network)
+}
+
+/// When a single ghost stands on an exit: at any of `exits`, and then again
+/// every `cycle_length` steps for the exits that lie inside the cycle.
+#[derive(Debug, PartialEq, Eq)]
+struct GhostPath {
+    cycle_start: usize,
+    cycle_length: usize,
+    /// Every step before the path first repeats itself where the ghost is on an exit.
+    exits: Vec<usize>,
+}
+
+impl GhostPath {
+    fn trace(network: &Network, start: NodeId) -> Result<Self> {
+        let instructions = network.instructions.as_bytes();
+
+        let mut seen = HashMap::new();
+        let mut exits = Vec::new();
+        let mut current = start;
+        let mut steps = 0;
+
+        loop {
+            let idx = steps % instructions.len();
+
+            match seen.entry((idx, current)) {
+                Vacant(e) => {
+                    e.insert(steps);
+                }
+                Occupied(e) => {
+                    return Ok(GhostPath {
+                        cycle_start: *e.get(),
+                        cycle_length: steps - *e.get(),
+                        exits,
+                    });
+                }
+            }
+
+            if current.is_ghost_end() {
+                exits.push(steps);
+            }
+
+            let node = network.node(current)?;
+
+            current = match instructions[idx] {
+                b'L' => node.left,
+                _ => node.right,
+            };
+
+            steps += 1;
+        }
+    }
+
+    fn is_exit(&self, step: usize) -> bool {
+        if step < self.cycle_start + self.cycle_length {
+            return self.exits.contains(&step);
+        }
+
+        let offset = (step - self.cycle_start) % self.cycle_length;
+
+        self.exits.contains(&(self.cycle_start + offset))
+    }
+}
+
+/// Finds the first step where every ghost stands on an exit at once.
+fn first_common_exit(paths: &[GhostPath]) -> Result<u128> {
+    let no_solution =
+        || Error::NoSolution("the ghosts never all stand on an exit at the same time".to_string());
+
+    // An answer before some ghost has started looping has to be one of that ghost's early exits
+    let mut early_exits = paths
+        .iter()
+        .flat_map(|path| path.exits.iter().filter(|&&step| step < path.cycle_start))
+        .copied()
+        .collect::<Vec<_>>();
+
+    early_exits.sort();
+
+    if let Some(step) = early_exits
+        .into_iter()
+        .find(|&step| paths.iter().all(|path| path.is_exit(step)))
+    {
+        return Ok(step as u128);
+    }
+
+    // Otherwise every ghost is looping, and the answer solves one congruence per ghost
+    let mut congruences = vec![(0u128, 1u128)];
+
+    for path in paths {
+        let m = path.cycle_length as u128;
+
+        let residues = path
+            .exits
+            .iter()
+            .filter(|&&step| step >= path.cycle_start)
+            .map(|&step| step as u128 % m)
+            .collect::<Vec<_>>();
+
+        congruences = congruences
+            .iter()
+            .flat_map(|&(a, n)| residues.iter().filter_map(move |&b| crt(a, n, b, m)))
+            .collect();
+
+        congruences.sort();
+        congruences.dedup();
+
+        if congruences.is_empty() {
+            return Err(no_solution());
+        }
+    }
+
+    let earliest = paths
+        .iter()
+        .map(|path| path.cycle_start as u128)
+        .max()
+        .unwrap_or(0);
+
+    congruences
+        .into_iter()
+        .map(|(a, n)| earliest + (a + n - earliest % n) % n)
+        .min()
+        .ok_or_else(no_solution)
+}
+
+#[cfg_attr(feature = "runner", aoc(day8, part1))]
+pub fn part1(network: &Network) -> Result<usize> {
+    let target = NodeId(*b"ZZZ");
+
+    let mut current = NodeId(*b"AAA");
+    let mut steps = 0;
+    let mut instructions = network.instructions.chars().cycle();
+    // After this many steps some node was reached twice at the same instruction,
+    // so the walk is going round a loop without ZZZ in it
+    let limit = network.nodes.len() * network.instructions.chars().count();
+
+    while current != target {
+        let node = network.node(current)?;
+
+        if steps > limit {
+            return Err(Error::NoSolution(
+                "ZZZ can't be reached from AAA".to_string(),
+            ));
+        }
+
+        match instructions.next() {
+            Some('L') => current = node.left,
+            Some('R') => current = node.right,
+            _ => return Err(Error::Invalid("there are no instructions".to_string())),
+        }
+
+        steps += 1;
+    }
+
+    Ok(steps)
+}
+
+#[cfg_attr(feature = "runner", aoc(day8, part2))]
+pub fn part2(network: &Network) -> Result<u128> {
+    let mut starts = network
+        .nodes
+        .keys()
+        .filter(|id| id.is_ghost_start())
+        .copied()
+        .collect::<Vec<_>>();
+
+    if starts.is_empty() {
+        return Err(Error::NoSolution(
+            "there are no ghost start nodes".to_string(),
+        ));
+    }
+
+    starts.sort_by_key(|id| id.0);
+
+    let paths = starts
+        .into_iter()
+        .map(|start| GhostPath::trace(network, start))
+        .collect::<Result<Vec<_>>>()?;
+
+    first_common_exit(&paths)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        part1(&generator("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)").unwrap()),
+        Ok(2),
+    );
+
+    assert_eq!(
+        part1(&generator("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap()),
+        Ok(6),
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        generator("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA ZZZ)\nZZZ = (ZZZ, ZZZ)").err(),
+        Some(Error::parse(4, 6, "expected \"(<left>, <right>)\""))
+    );
+
+    assert_eq!(
+        part1(&generator("LLR\n\nAAA = (BBB, BBB)\nBBB = (CCC, ZZZ)").unwrap()),
+        Err(Error::Invalid("node CCC is not in the network".to_string()))
+    );
+}
+
+#[test]
+fn test_part1_no_solution() {
+    assert_eq!(
+        part1(&generator("LR\n\nAAA = (BBB, AAA)\nBBB = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap()),
+        Err(Error::NoSolution(
+            "ZZZ can't be reached from AAA".to_string()
+        )),
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(
+        part2(&generator("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)").unwrap()),
+        Ok(6),
+    );
+
+    // Several exits per cycle, with cycle lengths that share a factor
+    assert_eq!(
+        part2(&generator("L\n\nAAA = (AAZ, AAZ)\nAAZ = (ABZ, ABZ)\nABZ = (ACC, ACC)\nACC = (AAA, AAA)\nBBA = (B1B, B1B)\nB1B = (B2B, B2B)\nB2B = (B3B, B3B)\nB3B = (B4B, B4B)\nB4B = (BBZ, BBZ)\nBBZ = (BBA, BBA)").unwrap()),
+        Ok(5),
+    );
+
+    // An exit that only comes up before the ghost starts looping
+    assert_eq!(
+        part2(&generator("L\n\nAAA = (AAZ, AAZ)\nAAZ = (AXX, AXX)\nAXX = (AXX, AXX)\nBBA = (BBZ, BBZ)\nBBZ = (BBZ, BBZ)").unwrap()),
+        Ok(1),
+    );
+}
+
+#[test]
+fn test_part2_no_solution() {
+    assert_eq!(
+        part2(&generator("L\n\nAAA = (AAZ, AAZ)\nAAZ = (AAA, AAA)\nBBA = (BBB, BBB)\nBBB = (BBZ, BBZ)\nBBZ = (BBB, BBB)").unwrap()),
+        Err(Error::NoSolution(
+            "the ghosts never all stand on an exit at the same time".to_string()
+        )),
+    );
+
+    assert_eq!(
+        part2(&generator("L\n\nAAA = (AAB, AAB)\nAAB = (AAA, AAA)").unwrap()),
+        Err(Error::NoSolution(
+            "the ghosts never all stand on an exit at the same time".to_string()
+        )),
+    );
+}
+
+#[test]
+fn test_ghost_path() {
+    let network =
+        generator("L\n\nAAA = (AAZ, AAZ)\nAAZ = (ABZ, ABZ)\nABZ = (ACC, ACC)\nACC = (AAZ, AAZ)")
+            .unwrap();
+
+    assert_eq!(
+        GhostPath::trace(&network, NodeId(*b"AAA")),
+        Ok(GhostPath {
+            cycle_start: 1,
+            cycle_length: 3,
+            exits: vec![1, 2],
+        })
+    );
+}
+
+/// Small networks where any node may be a start or an exit, with short instructions.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, sample::select, strategy::Strategy};
+
+    (1..9usize).prop_flat_map(|len| {
+        let instructions = vec(select(&['L', 'R'][..]), 1..5);
+        let nodes = vec((select(&['A', 'Z', 'X'][..]), 0..len, 0..len), len);
+
+        (instructions, nodes).prop_map(|(instructions, nodes)| {
+            let name = |idx: usize, suffix: char| {
+                let letter = (b'B' + idx as u8) as char;
+                format!("{}{}{}", letter, letter, suffix)
+            };
+
+            let nodes = nodes
+                .iter()
+                .enumerate()
+                .map(|(idx, &(suffix, left, right))| {
+                    format!(
+                        "{} = ({}, {})",
+                        name(idx, suffix),
+                        name(left, nodes[left].0),
+                        name(right, nodes[right].0)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            format!("{}\n\n{}", instructions.iter().collect::<String>(), nodes)
+        })
+    })
+}
+
+/// Walks every ghost at once until they all stand on an exit, giving up once
+/// the ghosts and instructions are back in a state they were in before.
+#[cfg(test)]
+fn ghost_steps_brute_force(input: &str) -> Option<u128> {
+    let (instructions, nodes) = input.split_once("\n\n").unwrap();
+    let instructions = instructions.as_bytes();
+
+    let network = nodes
+        .lines()
+        .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+        .collect::<HashMap<_, _>>();
+
+    let mut ghosts = network
+        .keys()
+        .filter(|id| id.ends_with('A'))
+        .copied()
+        .collect::<Vec<_>>();
+    let mut seen = std::collections::HashSet::new();
+
+    for steps in 0.. {
+        if ghosts.iter().all(|id| id.ends_with('Z')) {
+            return (!ghosts.is_empty()).then_some(steps);
+        }
+
+        let idx = steps as usize % instructions.len();
+
+        if !seen.insert((idx, ghosts.clone())) {
+            return None;
+        }
+
+        for ghost in ghosts.iter_mut() {
+            let (left, right) = network[ghost];
+            *ghost = if instructions[idx] == b'L' {
+                left
+            } else {
+                right
+            };
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_part2_matches_brute_force(input in arbitrary_input()) {
+        proptest::prop_assert_eq!(part2(&generator(&input).unwrap()).ok(), ghost_steps_brute_force(&input));
+    }
+
+    #[test]
+    fn test_part2_matches_brute_force_on_generated(seed in proptest::prelude::any::<u64>()) {
+        let settings = [("ghosts", 3), ("instructions", 3), ("factor", 2), ("primes", 7), ("tail", 2)];
+        let input = crate::gen::generate(2023, 8, seed, &settings);
+
+        proptest::prop_assert_eq!(part2(&generator(&input).unwrap()).ok(), ghost_steps_brute_force(&input));
+    }
+}
@@ -0,0 +1,352 @@
+use std::fmt::Display;
+
+use crate::{
+    parse::{self, Rows},
+    Error, Result,
+};
+
+/// An error for a step of the arithmetic, `what`, going past what an `i128` holds.
+fn overflow(what: impl Display) -> Error {
+    Error::Invalid(format!(
+        "{} doesn't fit in an i128, which holds at most {}",
+        what,
+        i128::MAX
+    ))
+}
+
+/// Turns a sequence of values into the polynomial through them, in place, in
+/// Newton's forward difference form: `f(n) = sum of differences[k] * C(n, k)`,
+/// where `differences[k]` is the k-th difference at index 0.
+fn fit(values: &mut [i128]) -> Result<()> {
+    // After each pass, the values from `k` on are the k-th differences
+    for k in 1..values.len() {
+        for i in (k..values.len()).rev() {
+            values[i] = values[i]
+                .checked_sub(values[i - 1])
+                .ok_or_else(|| overflow(format_args!("a difference of order {}", k)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The degree of the polynomial with these differences. The zero polynomial
+/// counts as degree 0.
+fn degree(differences: &[i128]) -> usize {
+    differences.iter().rposition(|&d| d != 0).unwrap_or(0)
+}
+
+/// The value at index `n` of the polynomial with these differences.
+fn predict(differences: &[i128], n: i64) -> Result<i128> {
+    let n = n as i128;
+    let mut total = 0i128;
+    // C(n, k), which is an integer for negative n as well
+    let mut binomial = 1i128;
+
+    // The differences past the degree are zero, and would only risk overflowing `binomial`
+    for (k, &difference) in differences.iter().enumerate().take(degree(differences) + 1) {
+        if k > 0 {
+            let k = k as i128;
+
+            binomial = binomial
+                .checked_mul(n - k + 1)
+                .ok_or_else(|| overflow(format_args!("C({}, {})", n, k)))?
+                / k;
+        }
+
+        total = difference
+            .checked_mul(binomial)
+            .and_then(|term| total.checked_add(term))
+            .ok_or_else(|| overflow(format_args!("the prediction at index {}", n)))?;
+    }
+
+    Ok(total)
+}
+
+/// The polynomial through a sequence of values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    differences: Vec<i128>,
+}
+
+impl Sequence {
+    pub fn new(values: &[i64]) -> Result<Self> {
+        let mut differences = values.iter().map(|&n| n as i128).collect::<Vec<_>>();
+        fit(&mut differences)?;
+
+        Ok(Sequence { differences })
+    }
+
+    /// The degree of the polynomial. The zero polynomial counts as degree 0.
+    pub fn degree(&self) -> usize {
+        degree(&self.differences)
+    }
+
+    /// The value at index `n`, where the first value given is at index 0.
+    /// Negative indices extrapolate backwards.
+    pub fn predict(&self, n: i64) -> Result<i128> {
+        predict(&self.differences, n)
+    }
+}
+
+/// Every line's numbers, one row per line.
+#[cfg_attr(feature = "runner", aoc_generator(day9))]
+pub fn generator(input: &str) -> Result<Rows<i64>> {
+    let mut sequences = Rows::default();
+
+    for line in parse::lines(input.as_bytes()) {
+        if line.rest().trim_ascii().is_empty() {
+            return Err(line.error("expected at least one number"));
+        }
+
+        for n in line.numbers() {
+            sequences.push(n?);
+        }
+
+        sequences.end_row();
+    }
+
+    Ok(sequences)
+}
+
+/// Sequences up to this long are fitted on the stack, so solving the puzzle
+/// input allocates nothing.
+const STACK_VALUES: usize = 32;
+
+/// The prediction at index `n` of the polynomial through `values`.
+fn extrapolate(values: &[i64], n: i64) -> Result<i128> {
+    let mut stack = [0i128; STACK_VALUES];
+    let mut heap = Vec::new();
+
+    let differences = match values.len() <= STACK_VALUES {
+        true => &mut stack[..values.len()],
+        false => {
+            heap.resize(values.len(), 0);
+            &mut heap[..]
+        }
+    };
+
+    for (difference, &value) in differences.iter_mut().zip(values) {
+        *difference = value as i128;
+    }
+
+    fit(differences)?;
+    predict(differences, n)
+}
+
+/// Adds up the prediction at `index` for every sequence, where `index` may
+/// depend on how many values the sequence has.
+fn sum_predictions(input: &Rows<i64>, index: impl Fn(usize) -> i64) -> Result<i128> {
+    input
+        .iter()
+        .enumerate()
+        .try_fold(0i128, |total, (idx, values)| {
+            let prediction = extrapolate(values, index(values.len())).map_err(|e| match e {
+                Error::Invalid(message) => Error::Invalid(format!("line {}: {}", idx + 1, message)),
+                e => e,
+            })?;
+
+            total.checked_add(prediction).ok_or_else(|| {
+                overflow(format_args!(
+                    "the sum of the predictions up to line {}",
+                    idx + 1
+                ))
+            })
+        })
+}
+
+#[cfg_attr(feature = "runner", aoc(day9, part1))]
+pub fn part1(input: &Rows<i64>) -> Result<i128> {
+    sum_predictions(input, |len| len as i64)
+}
+
+#[cfg_attr(feature = "runner", aoc(day9, part2))]
+pub fn part2(input: &Rows<i64>) -> Result<i128> {
+    sum_predictions(input, |_| -1)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(
+        part1(&generator("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45").unwrap()),
+        Ok(114)
+    );
+
+    assert_eq!(
+        part1(&generator("3 8 13 18 23 28 33 38 43 48 53 58 63 68 73 78 83 88 93 98 103").unwrap()),
+        Ok(108)
+    );
+
+    assert_eq!(
+        part1(&generator("-2 11 29 53 88 153 309 726 1835 4661 11532 27538 63421 141097 303903 635229 1292012 2564703 4984683 9517003 17906238").unwrap()),
+        Ok(33294945)
+    );
+
+    assert_eq!(
+        part1(
+            &generator("0 -1 -2 -3 -4 -5 -6 -7 -8 -9 -10 -11 -12 -13 -14 -15 -16 -17 -18 -19 -20")
+                .unwrap()
+        ),
+        Ok(-21)
+    );
+
+    assert_eq!(
+        part1(&generator("-4 -9 -14 -19 -24 -29 -34 -39 -44 -49 -54 -59 -64 -69 -74 -79 -84 -89 -94 -99 -104").unwrap()),
+        Ok(-109)
+    );
+
+    assert_eq!(
+        part1(&generator("18 21 22 22 37 109 314 760 1558 2736 4047 4598 2207 -7605 -33043 -87863 -193462 -381037 -693732 -1189230 -1944591").unwrap()),
+        Ok(-3067851)
+    );
+
+    assert_eq!(
+        part1(&generator("0 -1 -2 -3 -4 -5 -6 -7 -8 -9 -10 -11 -12 -13 -14 -15 -16 -17 -18 -19 -20\n-4 -9 -14 -19 -24 -29 -34 -39 -44 -49 -54 -59 -64 -69 -74 -79 -84 -89 -94 -99 -104\n18 21 22 22 37 109 314 760 1558 2736 4047 4598 2207 -7605 -33043 -87863 -193462 -381037 -693732 -1189230 -1944591").unwrap()),
+        Ok(-3067851 - 109 - 21)
+    );
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&generator("10  13  16  21  30  45").unwrap()), Ok(5));
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        generator("0 3 6 9 12 15\n1 3 six 10 15 21").err(),
+        Some(Error::parse(2, 5, "expected a number, found \"six\""))
+    );
+    assert_eq!(
+        generator("0 3\n  \n1").err(),
+        Some(Error::parse(2, 1, "expected at least one number"))
+    );
+}
+
+#[test]
+fn test_long_sequence() {
+    let ones = vec!["1"; 100].join(" ");
+
+    assert_eq!(part1(&generator(&ones).unwrap()), Ok(1));
+
+    let naturals = (0..100).map(|n| n.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(part2(&generator(&naturals.join(" ")).unwrap()), Ok(-1));
+}
+
+#[test]
+fn test_no_allocations() {
+    let input = generator(crate::gen::generate(2023, 9, 1, &[]).trim_end()).unwrap();
+    let (answers, allocations) = crate::memory::measure(|| (part1(&input), part2(&input)));
+
+    assert!(answers.0.is_ok() && answers.1.is_ok());
+    assert_eq!(allocations.map(|a| a.allocations), Some(0));
+}
+
+#[test]
+fn test_overflow() {
+    let alternating = |len| {
+        (0..len)
+            .map(|i| if i % 2 == 0 { i64::MAX } else { -i64::MAX }.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    assert_eq!(
+        part1(&generator(&format!("1 2 3\n{}", alternating(60))).unwrap()),
+        Err(Error::Invalid(format!(
+            "line 2: the prediction at index 60 doesn't fit in an i128, which holds at most {}",
+            i128::MAX
+        )))
+    );
+    assert_eq!(
+        part2(&generator(&alternating(70)).unwrap()),
+        Err(Error::Invalid(format!(
+            "line 1: a difference of order 65 doesn't fit in an i128, which holds at most {}",
+            i128::MAX
+        )))
+    );
+}
+
+#[test]
+fn test_sequence() {
+    let squares = Sequence::new(&[0, 1, 4, 9, 16]).unwrap();
+
+    assert_eq!(squares.degree(), 2);
+    assert_eq!(squares.predict(5), Ok(25));
+    assert_eq!(squares.predict(-3), Ok(9));
+    assert_eq!(
+        squares.predict(1_000_000_000),
+        Ok(1_000_000_000_000_000_000)
+    );
+
+    let cubes = Sequence::new(&[-8, -1, 0, 1, 8, 27]).unwrap();
+
+    assert_eq!(cubes.degree(), 3);
+    assert_eq!(cubes.predict(-98), Ok(-1_000_000));
+    assert_eq!(
+        cubes.predict(i64::MAX),
+        Err(overflow(format_args!(
+            "the prediction at index {}",
+            i64::MAX
+        )))
+    );
+
+    assert_eq!(Sequence::new(&[7]).unwrap().degree(), 0);
+    assert_eq!(Sequence::new(&[1; 100]).unwrap().predict(100), Ok(1));
+    assert_eq!(Sequence::new(&[0, 0, 0]).unwrap().predict(-5), Ok(0));
+    assert_eq!(
+        Sequence::new(&[i64::MIN, i64::MAX]).unwrap().predict(2),
+        Ok(i64::MAX as i128 * 3 + 1)
+    );
+}
+
+/// A few sequences of small numbers. Most aren't polynomials of low degree,
+/// so the rows of differences run all the way down.
+#[cfg(test)]
+fn arbitrary_input() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::{collection::vec, strategy::Strategy};
+
+    let line = vec(-50i64..50, 1..10).prop_map(|values| {
+        values
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+
+    vec(line, 1..6).prop_map(|lines| lines.join("\n"))
+}
+
+/// Stacks rows of differences until they are all zero, then extends every row
+/// by one value at each end, as the puzzle describes.
+#[cfg(test)]
+fn extrapolate_brute_force(values: &[i128]) -> (i128, i128) {
+    if values.iter().all(|&n| n == 0) {
+        return (0, 0);
+    }
+
+    let differences = values
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<_>>();
+    let (before, after) = extrapolate_brute_force(&differences);
+
+    (values[0] - before, values[values.len() - 1] + after)
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_parts_match_brute_force(input in arbitrary_input()) {
+        let (before, after) = input
+            .lines()
+            .map(|line| extrapolate_brute_force(&line.split(' ').map(|n| n.parse().unwrap()).collect::<Vec<_>>()))
+            .fold((0, 0), |(b, a), (before, after)| (b + before, a + after));
+
+        let sequences = generator(&input).unwrap();
+
+        proptest::prop_assert_eq!(part1(&sequences), Ok(after));
+        proptest::prop_assert_eq!(part2(&sequences), Ok(before));
+    }
+}
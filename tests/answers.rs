@@ -0,0 +1,60 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use advent_of_code::{
+    answers::{Answers, ANSWERS_PATH},
+    registry::SOLUTIONS,
+};
+
+/// Runs every registered solver against its real input and compares the result
+/// with the confirmed answer.
+#[test]
+fn test_known_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(root.join(ANSWERS_PATH)).unwrap();
+
+    let mut failures = Vec::new();
+    let mut unanswered = Vec::new();
+    let mut registered = BTreeSet::new();
+
+    for solution in SOLUTIONS {
+        let name = format!("{}/{}/{}", solution.year, solution.day, solution.part);
+        registered.insert((solution.year, solution.day, solution.part));
+
+        let Some(expected) = answers.get(solution.year, solution.day, solution.part) else {
+            unanswered.push(name);
+            continue;
+        };
+
+        let input = match fs::read_to_string(root.join(solution.input_path())) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: couldn't read the input: {}", name, e));
+                continue;
+            }
+        };
+
+        match solution.solve(&input) {
+            Ok(answer) if answer == expected => {}
+            Ok(answer) => failures.push(format!("{}: expected {}, got {}", name, expected, answer)),
+            Err(e) => failures.push(format!(
+                "{}: expected {}, got an error: {}",
+                name, expected, e
+            )),
+        }
+    }
+
+    for ((year, day, part), _) in answers.iter() {
+        if !registered.contains(&(year, day, part)) {
+            failures.push(format!(
+                "{}/{}/{}: has an answer but no solver",
+                year, day, part
+            ));
+        }
+    }
+
+    if !unanswered.is_empty() {
+        println!("no confirmed answer for: {}", unanswered.join(", "));
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,52 @@
+/* Calls the solvers through include/advent_of_code.h, the way other languages would.
+ * tests/ffi.rs builds it against the library and runs it. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "advent_of_code.h"
+
+static const char EXAMPLE[] = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+static int failures = 0;
+
+static void expect(AocPuzzle puzzle, const char *input, bool ok, const char *text) {
+    AocAnswer answer = aoc_solve(puzzle, (const uint8_t *)input, strlen(input));
+
+    if (answer.ok != ok || strcmp(answer.text, text) != 0) {
+        fprintf(stderr, "%u/%u/%u: expected %s \"%s\", got %s \"%s\"\n", puzzle.year, puzzle.day,
+                puzzle.part, ok ? "answer" : "error", text, answer.ok ? "answer" : "error",
+                answer.text);
+        failures++;
+    }
+
+    aoc_answer_free(answer);
+}
+
+int main(void) {
+    expect((AocPuzzle){2023, 6, 1}, EXAMPLE, true, "288");
+    expect((AocPuzzle){2023, 6, 2}, EXAMPLE, true, "71503");
+    expect((AocPuzzle){2023, 25, 1}, EXAMPLE, false, "2023/25/1 has no solver");
+
+    /* Every registered puzzle answers, or says why it can't */
+    size_t count = aoc_puzzle_count();
+
+    for (size_t i = 0; i < count; i++) {
+        AocPuzzle puzzle = aoc_puzzle(i);
+        AocAnswer answer = aoc_solve(puzzle, NULL, 0);
+
+        if (puzzle.year == 0 || answer.text == NULL) {
+            fprintf(stderr, "puzzle %zu of %zu is missing\n", i, count);
+            failures++;
+        }
+
+        aoc_answer_free(answer);
+    }
+
+    if (aoc_puzzle(count).year != 0) {
+        fprintf(stderr, "found a puzzle past the last one\n");
+        failures++;
+    }
+
+    return failures == 0 ? 0 : 1;
+}
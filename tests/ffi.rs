@@ -0,0 +1,72 @@
+//! Checks that the C header matches `src/ffi.rs`, then builds `tests/ffi.c`
+//! against the library and runs it.
+#![cfg(unix)]
+
+use std::{env, fs, path::Path, process::Command};
+
+const HEADER_PATH: &str = "include/advent_of_code.h";
+
+/// Set to rewrite the header instead of failing when it is out of date.
+const UPDATE_VAR: &str = "UPDATE_HEADER";
+
+#[test]
+fn test_header_is_up_to_date() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src/ffi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    let header = String::from_utf8(header).unwrap();
+
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::create_dir_all(root.join("include")).unwrap();
+        fs::write(root.join(HEADER_PATH), &header).unwrap();
+    }
+
+    assert!(
+        fs::read_to_string(root.join(HEADER_PATH)).ok() == Some(header),
+        "{} is out of date, regenerate it with `{}=1 cargo test --test ffi`",
+        HEADER_PATH,
+        UPDATE_VAR
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+
+    // Cargo leaves the shared library next to this test's executable
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+
+    let compiled = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .args(["-std=c99", "-Wall", "-Werror", "-o"])
+        .arg(&program)
+        .arg(root.join("tests/ffi.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-ladvent_of_code")
+        .status()
+        .unwrap();
+    assert!(compiled.success());
+
+    // Cargo's library path can hold an older copy of the library, and it wins over the rpath
+    let output = Command::new(&program)
+        .env_remove("LD_LIBRARY_PATH")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,42 @@
+//! Runs headless under Node with
+//! `cargo test --target wasm32-unknown-unknown --no-default-features --features wasm --test wasm`,
+//! and as ordinary tests with `cargo test --features wasm`.
+#![cfg(feature = "wasm")]
+
+use advent_of_code::wasm;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const EXAMPLE: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_solve() {
+    assert_eq!(wasm::solve_year(2023, 6, 1, EXAMPLE), Ok("288".to_string()));
+    assert_eq!(
+        wasm::solve_year(2023, 6, 2, EXAMPLE),
+        Ok("71503".to_string())
+    );
+    assert_eq!(
+        wasm::solve(6, 2, EXAMPLE),
+        wasm::solve_year(2023, 6, 2, EXAMPLE)
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_solve_errors() {
+    assert_eq!(
+        wasm::solve_year(2023, 25, 1, EXAMPLE),
+        Err("2023/25/1 has no solver".to_string())
+    );
+    assert!(wasm::solve_year(2023, 6, 1, "Time: 7\nSpeed: 9")
+        .unwrap_err()
+        .starts_with("line 2"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_days() {
+    let days = wasm::days();
+
+    assert_eq!(days.first().map(String::as_str), Some("2023/1"));
+    assert!(days.contains(&"2023/9".to_string()));
+    assert!(days.windows(2).all(|pair| pair[0] != pair[1]));
+}